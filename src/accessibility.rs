@@ -0,0 +1,304 @@
+use bevy::prelude::*;
+use crate::focus::Focusable;
+use crate::GameHelper;
+use crate::helper::ModalMenu;
+use crate::locale::{ChangeLanguageEvent, Localization};
+use crate::ui::Rect;
+
+// just over the audio menu, so whichever is on top is the one that gets clicked
+const MENU_LEVEL: f32 = 86.;
+const OPTION_SIZE: Vec2 = Vec2::new(440., 40.);
+const OPTION_SPACING: f32 = 56.;
+const TEXT_SCALES: [f32; 3] = [1., 1.25, 1.5];
+
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<AccessibilitySettings>()
+            .add_system(toggle_accessibility_menu)
+            .add_system(use_accessibility_menu)
+            .add_system(render_accessibility_menu.after(use_accessibility_menu))
+            .add_system_to_stage(CoreStage::PostUpdate, apply_accessible_text);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessibilitySettings {
+    pub text_scale: f32,
+    /// Uses the bundled JetBrainsMono instead of the pixel font.
+    pub readable_font: bool,
+    pub high_contrast: bool,
+    /// Shows dialogue all at once and holds failure marks and the boss clock steady instead of blinking.
+    pub reduced_motion: bool,
+}
+
+impl Default for AccessibilitySettings {
+    fn default() -> Self {
+        AccessibilitySettings {
+            text_scale: 1.,
+            readable_font: false,
+            high_contrast: false,
+            reduced_motion: false,
+        }
+    }
+}
+
+impl AccessibilitySettings {
+    fn next_text_scale(&self) -> f32 {
+        let index = TEXT_SCALES.iter().position(|scale| *scale == self.text_scale).unwrap_or(0);
+        TEXT_SCALES[(index + 1) % TEXT_SCALES.len()]
+    }
+
+    fn text_color(&self, color: Color) -> Color {
+        if !self.high_contrast {
+            return color;
+        }
+
+        // every text colour goes to black or white, whichever is closer, so coloured titles and
+        // labels don't stay brown on the parchment
+        let [r, g, b, a] = color.as_rgba_f32();
+        let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        if luminance < 0.5 {
+            Color::rgba(0., 0., 0., a)
+        } else {
+            Color::rgba(1., 1., 1., a)
+        }
+    }
+}
+
+//==================================================================================================
+//                          Text
+//==================================================================================================
+
+/// How a text section was styled by the code that made it, and what it was last changed to here.
+/// If the section no longer matches what was applied, whoever owns it changed it and that becomes
+/// the new base.
+#[derive(Clone, Copy)]
+struct SectionStyle {
+    base_size: f32,
+    base_color: Color,
+    applied_size: f32,
+    applied_color: Color,
+}
+
+#[derive(Component)]
+pub struct AccessibleText(Vec<SectionStyle>);
+
+impl AccessibleText {
+    fn from_text(text: &Text) -> Self {
+        AccessibleText(text.sections.iter()
+            .map(|section| SectionStyle {
+                base_size: section.style.font_size,
+                base_color: section.style.color,
+                applied_size: section.style.font_size,
+                applied_color: section.style.color,
+            })
+            .collect())
+    }
+}
+
+fn apply_accessible_text(
+    mut commands: Commands,
+    settings: Res<AccessibilitySettings>,
+    mut texts: Query<(Entity, &mut Text, Option<&mut AccessibleText>)>,
+) {
+    for (entity, mut text, accessible) in texts.iter_mut() {
+        let mut styles = match &accessible {
+            Some(accessible) if accessible.0.len() == text.sections.len() => accessible.0.clone(),
+            _ => AccessibleText::from_text(&text).0,
+        };
+
+        for (section, style) in text.sections.iter().zip(styles.iter_mut()) {
+            if section.style.font_size != style.applied_size {
+                style.base_size = section.style.font_size;
+            }
+            if section.style.color != style.applied_color {
+                style.base_color = section.style.color;
+            }
+            style.applied_size = style.base_size * settings.text_scale;
+            style.applied_color = settings.text_color(style.base_color);
+        }
+
+        let changed = text.sections.iter().zip(styles.iter())
+            .any(|(section, style)| section.style.font_size != style.applied_size || section.style.color != style.applied_color);
+        if changed {
+            for (section, style) in text.sections.iter_mut().zip(styles.iter()) {
+                section.style.font_size = style.applied_size;
+                section.style.color = style.applied_color;
+            }
+        }
+
+        match accessible {
+            Some(mut accessible) => accessible.0 = styles,
+            None => {
+                commands.entity(entity).insert(AccessibleText(styles));
+            }
+        }
+    }
+}
+
+//==================================================================================================
+//                          Menu
+//==================================================================================================
+
+/// Opened and closed with F1.
+#[derive(Component)]
+pub struct AccessibilityMenu;
+
+#[derive(Component)]
+pub struct AccessibilityTitle;
+
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum AccessibilityOption {
+    TextSize,
+    Font,
+    Contrast,
+    Motion,
+    Language,
+}
+
+impl AccessibilityOption {
+    const ALL: [AccessibilityOption; 5] = [AccessibilityOption::TextSize, AccessibilityOption::Font, AccessibilityOption::Contrast, AccessibilityOption::Motion, AccessibilityOption::Language];
+
+    fn label(&self, settings: &AccessibilitySettings, localization: &Localization) -> String {
+        let on_off = |on: bool| localization.text(if on { "On" } else { "Off" }).to_string();
+        let value = match self {
+            AccessibilityOption::TextSize => format!("{}%", (settings.text_scale * 100.).round()),
+            AccessibilityOption::Font => localization.text(if settings.readable_font { "Readable" } else { "Pixel" }).to_string(),
+            AccessibilityOption::Contrast => on_off(settings.high_contrast),
+            AccessibilityOption::Motion => on_off(settings.reduced_motion),
+            AccessibilityOption::Language => localization.language().name().to_string(),
+        };
+        let name = match self {
+            AccessibilityOption::TextSize => "Text size",
+            AccessibilityOption::Font => "Font",
+            AccessibilityOption::Contrast => "High contrast",
+            AccessibilityOption::Motion => "Reduced motion",
+            AccessibilityOption::Language => "Language",
+        };
+        format!("{}: {}", localization.text(name), value)
+    }
+
+    fn toggle(&self, settings: &mut AccessibilitySettings, localization: &Localization, change_language_event: &mut EventWriter<ChangeLanguageEvent>) {
+        match self {
+            AccessibilityOption::TextSize => settings.text_scale = settings.next_text_scale(),
+            AccessibilityOption::Font => settings.readable_font = !settings.readable_font,
+            AccessibilityOption::Contrast => settings.high_contrast = !settings.high_contrast,
+            AccessibilityOption::Motion => settings.reduced_motion = !settings.reduced_motion,
+            AccessibilityOption::Language => change_language_event.send(ChangeLanguageEvent(localization.language().next())),
+        }
+    }
+}
+
+fn spawn_accessibility_menu(commands: &mut Commands, asset_server: &AssetServer, settings: &AccessibilitySettings, localization: &Localization) {
+    let font = asset_server.load("fonts/pixel_font.ttf");
+
+    let title = commands.spawn_bundle(Text2dBundle {
+        text: Text::from_section(localization.text("Accessibility"), TextStyle {
+            font: font.clone(),
+            font_size: 40.,
+            color: Color::WHITE,
+        }).with_alignment(TextAlignment::CENTER),
+        transform: Transform::from_xyz(0., 170., 1.),
+        ..default()
+    })
+        .insert(AccessibilityTitle)
+        .id();
+
+    let mut children = vec![title];
+    for (i, option) in AccessibilityOption::ALL.into_iter().enumerate() {
+        let entity = commands.spawn_bundle(Text2dBundle {
+            text: Text::from_section(option.label(settings, localization), TextStyle {
+                font: font.clone(),
+                font_size: 28.,
+                color: Color::WHITE,
+            }).with_alignment(TextAlignment::CENTER),
+            transform: Transform::from_xyz(0., 90. - OPTION_SPACING * i as f32, 1.),
+            ..default()
+        })
+            .insert(option)
+            .insert(Focusable::sized(OPTION_SIZE))
+            .id();
+        children.push(entity);
+    }
+
+    commands.spawn_bundle(SpriteBundle {
+        sprite: Sprite {
+            color: Color::rgba(0., 0., 0., 0.85),
+            custom_size: Some(Vec2::new(560., 420.)),
+            ..default()
+        },
+        transform: Transform::from_xyz(0., 0., MENU_LEVEL),
+        ..default()
+    })
+        .insert(AccessibilityMenu)
+        .insert(ModalMenu)
+        .insert(Name::new("Accessibility Menu"))
+        .push_children(&children);
+}
+
+//==================================================================================================
+//                          Systems
+//==================================================================================================
+
+fn toggle_accessibility_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    keys: Res<Input<KeyCode>>,
+    settings: Res<AccessibilitySettings>,
+    localization: Res<Localization>,
+    menu: Query<Entity, With<AccessibilityMenu>>,
+) {
+    if !keys.just_pressed(KeyCode::F1) {
+        return;
+    }
+
+    if menu.is_empty() {
+        spawn_accessibility_menu(&mut commands, &asset_server, &settings, &localization);
+    } else {
+        for entity in menu.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn use_accessibility_menu(
+    game_helper: Res<GameHelper>,
+    mouse: Res<Input<MouseButton>>,
+    mut settings: ResMut<AccessibilitySettings>,
+    localization: Res<Localization>,
+    menu: Query<Entity, With<AccessibilityMenu>>,
+    options: Query<(&GlobalTransform, &AccessibilityOption)>,
+    mut change_language_event: EventWriter<ChangeLanguageEvent>,
+) {
+    if !mouse.just_pressed(MouseButton::Left) || !menu.iter().any(|menu| game_helper.is_top_menu(menu)) {
+        return;
+    }
+
+    let clicked = options.iter()
+        .find(|(transform, _)| Rect::from_center(transform.translation().truncate(), OPTION_SIZE).is_within(game_helper.mouse_world_pos()));
+    if let Some((_, option)) = clicked {
+        option.toggle(&mut settings, &localization, &mut change_language_event);
+    }
+}
+
+fn render_accessibility_menu(
+    settings: Res<AccessibilitySettings>,
+    localization: Res<Localization>,
+    mut title: Query<&mut Text, (With<AccessibilityTitle>, Without<AccessibilityOption>)>,
+    mut options: Query<(&mut Text, &AccessibilityOption)>,
+) {
+    if !settings.is_changed() && !localization.is_changed() {
+        return;
+    }
+
+    // the language can be changed from inside the menu, so the title is redone too
+    for mut text in title.iter_mut() {
+        text.sections[0].value = localization.text("Accessibility").to_string();
+    }
+    for (mut text, option) in options.iter_mut() {
+        text.sections[0].value = option.label(&settings, &localization);
+    }
+}
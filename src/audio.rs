@@ -1,34 +1,53 @@
+pub mod music;
+pub mod settings;
+pub mod sfx;
+
 use bevy::prelude::*;
 use bevy_kira_audio::prelude::*;
-use crate::boss_fight::SetupBossFightEvent;
+use crate::audio::settings::{apply_audio_settings, AudioSettings, render_audio_settings_menu, save_audio_settings, toggle_audio_settings_menu, use_audio_settings_menu, Volume};
+use crate::audio::sfx::{play_sfx, PlaySfxEvent, sfx_for_crafting};
+use crate::audio::music::{change_music, layer_boss_music, layer_music, MusicChangeEvent, MusicChannel, MusicLayerEvent, MusicPlayer, preload_music, start_music, update_music_volume};
 use crate::game::GameManager;
 use crate::npc::{Npc, NpcKind, Say};
 
 pub struct AudioPlugin;
 
-#[derive(Clone)]
-pub enum MusicTrack {
-    Background,
-    Boss,
-}
-
 impl Plugin for AudioPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_event::<SayEvent>()
             .add_event::<MusicChangeEvent>()
+            .add_event::<MusicLayerEvent>()
+            .add_event::<PlaySfxEvent>()
             .init_resource::<AudioManager>()
+            .init_resource::<MusicPlayer>()
+            .insert_resource(AudioSettings::load())
             .add_plugin(bevy_kira_audio::AudioPlugin)
             .add_audio_channel::<DialogueChannel>()
             .add_audio_channel::<SfxChannel>()
-            .add_startup_system(start_background_audio)
+            .add_audio_channel::<MusicChannel>()
+            .add_startup_system(preload_music)
+            .add_startup_system(start_music)
+            .add_system(toggle_audio_settings_menu)
+            .add_system(use_audio_settings_menu)
+            .add_system(render_audio_settings_menu.after(use_audio_settings_menu))
+            .add_system(apply_audio_settings.after(use_audio_settings_menu))
+            .add_system(save_audio_settings.after(use_audio_settings_menu))
             .add_system(play_dialogue_voice)
             .add_system(stop_dialogue_voice)
-            .add_system(start_boss_audio);
+            .add_system(sfx_for_crafting)
+            .add_system(play_sfx.after(sfx_for_crafting))
+            .add_system(change_music)
+            .add_system(layer_boss_music)
+            .add_system(layer_music.after(layer_boss_music).after(change_music))
+            .add_system(update_music_volume.after(layer_music).after(stop_dialogue_voice));
     }
 }
 
-struct DialogueChannel;
+// voice loops are quiet so the text blips don't drown out the music
+const VOICE_VOLUME: f64 = 0.08;
+
+pub struct DialogueChannel;
 pub struct SfxChannel;
 
 struct AudioManager {
@@ -45,65 +64,44 @@ impl Default for AudioManager {
     }
 }
 
-impl AudioManager {}
-
-pub struct SayEvent(pub f64);
-
-fn start_background_audio(asset_server: Res<AssetServer>, audio: Res<Audio>) {
-    println!("start bg music");
-    audio.play(asset_server.load("sounds/tavern_music.wav")).looped()
-        .loop_from(7.0)
-        .with_volume(0.2);
-}
-
-fn start_boss_audio(
-    asset_server: Res<AssetServer>,
-    audio: Res<Audio>,
-    mut on_boss_enter: EventReader<MusicChangeEvent>
-) {
-    if !on_boss_enter.is_empty() {
-        println!("change audio");
-        audio.stop();
-        for event in on_boss_enter.iter() {
-            match event.0 {
-                MusicTrack::Background => {
-                    audio.play(asset_server.load("sounds/background_music.wav")).looped()
-                        .loop_from(7.0)
-                        .with_volume(0.2);
-                },
-                MusicTrack::Boss => {
-                    audio.play(asset_server.load("sounds/boss_music.wav")).looped()
-                        .loop_from(2.0)
-                        .with_volume(0.15);
-                },
-            }
-        }
+impl AudioManager {
+    /// Whether someone is still talking, so the music can make room for them.
+    fn is_speaking(&self) -> bool {
+        self.say_progress < self.total_say_duration
     }
 }
 
+pub struct SayEvent(pub f64);
+
 fn play_dialogue_voice(
     mut say_event: EventReader<SayEvent>,
     game: Res<GameManager>,
     dialogue: Res<AudioChannel<DialogueChannel>>,
     asset_server: Res<AssetServer>,
     audio_manager: ResMut<AudioManager>,
+    settings: Res<AudioSettings>,
     // say_query: Query<Say>
 ) {
     // todo: only play once
     for _ in say_event.iter() {
         dialogue.stop();
-        match game.npc_data.get_current_npc().unwrap().kind {
+        let volume = VOICE_VOLUME * settings.scale(Volume::Voice);
+        let speaker = match game.npc_data.npc_at(game.npc_data.speaker()) {
+            Some(npc) => npc,
+            None => continue,
+        };
+        match speaker.kind {
             NpcKind::Squee => {
-                dialogue.play(asset_server.load("sounds/squee_voice.wav")).looped().with_volume(0.08);
+                dialogue.play(asset_server.load("sounds/squee_voice.wav")).looped().with_volume(volume);
             }
             NpcKind::Conrad => {
-                dialogue.play(asset_server.load("sounds/conrad_voice.wav")).looped().with_volume(0.08);
+                dialogue.play(asset_server.load("sounds/conrad_voice.wav")).looped().with_volume(volume);
             }
             NpcKind::Pumkinhead => {
-                dialogue.play(asset_server.load("sounds/pumpkinhead_voice.wav")).looped().with_volume(0.08);
+                dialogue.play(asset_server.load("sounds/pumpkinhead_voice.wav")).looped().with_volume(volume);
             }
             NpcKind::Gordon => {
-                dialogue.play(asset_server.load("sounds/gordon_voice.wav")).looped().with_volume(0.08);
+                dialogue.play(asset_server.load("sounds/gordon_voice.wav")).looped().with_volume(volume);
             }
         }
     }
@@ -130,5 +128,3 @@ fn stop_dialogue_voice(
         dialogue.stop();
     }
 }
-
-pub struct MusicChangeEvent(pub MusicTrack);
\ No newline at end of file
@@ -0,0 +1,247 @@
+use std::time::Duration;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_kira_audio::prelude::*;
+use crate::audio::AudioManager;
+use crate::audio::settings::{AudioSettings, Volume};
+use crate::boss_fight::{BossTimerThresholdEvent, StartBossPhaseEvent};
+
+pub const DEFAULT_FADE_SECONDS: f32 = 1.5;
+// how loud the music stays while someone is talking
+const DUCK_VOLUME: f64 = 0.4;
+const VOLUME_FADE_SECONDS: f32 = 0.5;
+const DRUMS_BELOW_SECONDS: u64 = 60;
+
+pub struct MusicChannel;
+
+/// A stem that plays in sync with its track, silent until it is layered in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MusicStem {
+    pub name: &'static str,
+    pub path: &'static str,
+}
+
+impl MusicStem {
+    pub const fn new(name: &'static str, path: &'static str) -> Self {
+        MusicStem { name, path }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MusicTrack {
+    pub name: &'static str,
+    pub path: &'static str,
+    pub loop_from: f64,
+    pub volume: f64,
+    pub stems: &'static [MusicStem],
+}
+
+impl MusicTrack {
+    pub const TAVERN: MusicTrack = MusicTrack::new("Tavern", "sounds/tavern_music.wav", 7.0, 0.2);
+    pub const BACKGROUND: MusicTrack = MusicTrack::new("Background", "sounds/background_music.wav", 7.0, 0.2);
+    pub const BOSS: MusicTrack = MusicTrack::new("Boss", "sounds/boss_music.wav", 2.0, 0.15)
+        .with_stems(&[MusicStem::new("Drums", "sounds/boss_music_drums.wav")]);
+
+    /// Every track in the game, loaded up front so changing tracks doesn't wait on the disk.
+    pub const ALL: [MusicTrack; 3] = [MusicTrack::TAVERN, MusicTrack::BACKGROUND, MusicTrack::BOSS];
+
+    pub const fn new(name: &'static str, path: &'static str, loop_from: f64, volume: f64) -> Self {
+        MusicTrack {
+            name,
+            path,
+            loop_from,
+            volume,
+            stems: &[],
+        }
+    }
+
+    pub const fn with_stems(mut self, stems: &'static [MusicStem]) -> Self {
+        self.stems = stems;
+        self
+    }
+}
+
+/// Crossfades to `track` over `fade` seconds. Does nothing if it is already playing.
+#[derive(Debug)]
+pub struct MusicChangeEvent {
+    pub track: MusicTrack,
+    pub fade: f32,
+}
+
+impl MusicChangeEvent {
+    pub fn to(track: MusicTrack) -> Self {
+        MusicChangeEvent { track, fade: DEFAULT_FADE_SECONDS }
+    }
+
+    pub fn with_fade(mut self, seconds: f32) -> Self {
+        self.fade = seconds;
+        self
+    }
+}
+
+/// Fades a stem of the current track in or out.
+#[derive(Debug)]
+pub struct MusicLayerEvent {
+    pub stem: &'static str,
+    pub enabled: bool,
+}
+
+//==================================================================================================
+//                          Player
+//==================================================================================================
+
+struct MusicInstance {
+    handle: Handle<AudioInstance>,
+    // the instance only exists once kira has started it, until then the volume can't be set
+    applied_volume: Option<f64>,
+}
+
+impl MusicInstance {
+    fn set_volume(&mut self, instances: &mut Assets<AudioInstance>, volume: f64) {
+        if self.applied_volume == Some(volume) {
+            return;
+        }
+        if let Some(instance) = instances.get_mut(&self.handle) {
+            instance.set_volume(volume, fade(VOLUME_FADE_SECONDS));
+            self.applied_volume = Some(volume);
+        }
+    }
+
+    fn stop(&self, instances: &mut Assets<AudioInstance>, seconds: f32) {
+        if let Some(instance) = instances.get_mut(&self.handle) {
+            instance.stop(fade(seconds));
+        }
+    }
+}
+
+struct PlayingTrack {
+    track: MusicTrack,
+    main: MusicInstance,
+    stems: Vec<(MusicStem, MusicInstance, bool)>,
+}
+
+#[derive(Default)]
+pub struct MusicPlayer {
+    current: Option<PlayingTrack>,
+    sources: HashMap<&'static str, Handle<AudioSource>>,
+}
+
+impl MusicPlayer {
+    fn source(&mut self, asset_server: &AssetServer, path: &'static str) -> Handle<AudioSource> {
+        self.sources.entry(path).or_insert_with(|| asset_server.load(path)).clone()
+    }
+
+    pub fn current_track(&self) -> Option<MusicTrack> {
+        self.current.as_ref().map(|playing| playing.track)
+    }
+}
+
+fn fade(seconds: f32) -> AudioTween {
+    AudioTween::linear(Duration::from_secs_f32(seconds))
+}
+
+//==================================================================================================
+//                          Systems
+//==================================================================================================
+
+pub fn preload_music(asset_server: Res<AssetServer>, mut player: ResMut<MusicPlayer>) {
+    for track in MusicTrack::ALL {
+        player.source(&asset_server, track.path);
+        for stem in track.stems {
+            player.source(&asset_server, stem.path);
+        }
+    }
+}
+
+pub fn start_music(mut music_change_event: EventWriter<MusicChangeEvent>) {
+    music_change_event.send(MusicChangeEvent::to(MusicTrack::TAVERN).with_fade(0.));
+}
+
+pub fn change_music(
+    asset_server: Res<AssetServer>,
+    channel: Res<AudioChannel<MusicChannel>>,
+    mut instances: ResMut<Assets<AudioInstance>>,
+    settings: Res<AudioSettings>,
+    mut player: ResMut<MusicPlayer>,
+    mut music_change_event: EventReader<MusicChangeEvent>,
+) {
+    for event in music_change_event.iter() {
+        if player.current_track().is_some_and(|track| track.name == event.track.name) {
+            continue;
+        }
+
+        if let Some(old) = player.current.take() {
+            old.main.stop(&mut instances, event.fade);
+            for (_, stem, _) in old.stems.iter() {
+                stem.stop(&mut instances, event.fade);
+            }
+        }
+
+        let track = event.track;
+        let play = |source: Handle<AudioSource>, volume: f64| MusicInstance {
+            handle: channel.play(source).looped()
+                .loop_from(track.loop_from)
+                .with_volume(volume)
+                .fade_in(fade(event.fade))
+                .handle(),
+            applied_volume: Some(volume),
+        };
+
+        let main = play(player.source(&asset_server, track.path), track.volume * settings.scale(Volume::Music));
+        let stems = track.stems.iter()
+            .map(|stem| (*stem, play(player.source(&asset_server, stem.path), 0.), false))
+            .collect();
+
+        player.current = Some(PlayingTrack { track, main, stems });
+    }
+}
+
+pub fn layer_music(
+    mut player: ResMut<MusicPlayer>,
+    mut music_layer_event: EventReader<MusicLayerEvent>,
+) {
+    for event in music_layer_event.iter() {
+        if let Some(playing) = player.current.as_mut() {
+            for (stem, _, enabled) in playing.stems.iter_mut() {
+                if stem.name == event.stem {
+                    *enabled = event.enabled;
+                }
+            }
+        }
+    }
+}
+
+/// Brings the drums in for the last minute of a boss course.
+pub fn layer_boss_music(
+    mut threshold_event: EventReader<BossTimerThresholdEvent>,
+    mut start_boss_phase_event: EventReader<StartBossPhaseEvent>,
+    mut music_layer_event: EventWriter<MusicLayerEvent>,
+) {
+    for _ in start_boss_phase_event.iter() {
+        music_layer_event.send(MusicLayerEvent { stem: "Drums", enabled: false });
+    }
+
+    for event in threshold_event.iter() {
+        if event.0 == DRUMS_BELOW_SECONDS {
+            music_layer_event.send(MusicLayerEvent { stem: "Drums", enabled: true });
+        }
+    }
+}
+
+/// Keeps every instance at the volume it should be at, following the settings and ducking the music under dialogue.
+pub(super) fn update_music_volume(
+    audio_manager: Res<AudioManager>,
+    settings: Res<AudioSettings>,
+    mut instances: ResMut<Assets<AudioInstance>>,
+    mut player: ResMut<MusicPlayer>,
+) {
+    let duck = if audio_manager.is_speaking() { DUCK_VOLUME } else { 1. };
+
+    if let Some(playing) = player.current.as_mut() {
+        let volume = playing.track.volume * settings.scale(Volume::Music) * duck;
+        playing.main.set_volume(&mut instances, volume);
+        for (_, stem, enabled) in playing.stems.iter_mut() {
+            stem.set_volume(&mut instances, if *enabled { volume } else { 0. });
+        }
+    }
+}
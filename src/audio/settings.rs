@@ -0,0 +1,411 @@
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use crate::audio::{DialogueChannel, VOICE_VOLUME};
+use crate::GameHelper;
+use crate::helper::ModalMenu;
+use crate::locale::Localization;
+use crate::ui::{Rect, UiData};
+
+const SETTINGS_FILE: &str = "audio_settings.cfg";
+const MENU_LEVEL: f32 = 85.;
+const SLIDER_SIZE: Vec2 = Vec2::new(240., 12.);
+// the slider can be grabbed a little above and below the track
+const SLIDER_GRAB_HEIGHT: f32 = 32.;
+const KNOB_SIZE: Vec2 = Vec2::new(12., 28.);
+const ROW_SPACING: f32 = 56.;
+const SLIDER_STEP: f64 = 0.05;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Volume {
+    Master,
+    Music,
+    Voice,
+    Sfx,
+}
+
+impl Volume {
+    pub const ALL: [Volume; 4] = [Volume::Master, Volume::Music, Volume::Voice, Volume::Sfx];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Volume::Master => "Master",
+            Volume::Music => "Music",
+            Volume::Voice => "Voices",
+            Volume::Sfx => "Effects",
+        }
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            Volume::Master => "master",
+            Volume::Music => "music",
+            Volume::Voice => "voice",
+            Volume::Sfx => "sfx",
+        }
+    }
+}
+
+/// How loud each part of the game is. Kept between runs in `audio_settings.cfg`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AudioSettings {
+    pub master: f64,
+    pub music: f64,
+    pub voice: f64,
+    pub sfx: f64,
+    pub muted: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        AudioSettings {
+            master: 1.,
+            music: 1.,
+            voice: 1.,
+            sfx: 1.,
+            muted: false,
+        }
+    }
+}
+
+impl AudioSettings {
+    pub fn get(&self, volume: Volume) -> f64 {
+        match volume {
+            Volume::Master => self.master,
+            Volume::Music => self.music,
+            Volume::Voice => self.voice,
+            Volume::Sfx => self.sfx,
+        }
+    }
+
+    pub fn set(&mut self, volume: Volume, value: f64) {
+        let value = value.clamp(0., 1.);
+        match volume {
+            Volume::Master => self.master = value,
+            Volume::Music => self.music = value,
+            Volume::Voice => self.voice = value,
+            Volume::Sfx => self.sfx = value,
+        }
+    }
+
+    /// What a sound of this kind gets multiplied by once master and mute are taken into account.
+    pub fn scale(&self, volume: Volume) -> f64 {
+        if self.muted {
+            return 0.;
+        }
+        match volume {
+            Volume::Master => self.master,
+            _ => self.master * self.get(volume),
+        }
+    }
+
+    pub fn to_config(&self) -> String {
+        let mut config = String::new();
+        for volume in Volume::ALL {
+            config += &format!("{} = {}\n", volume.key(), self.get(volume));
+        }
+        config += &format!("muted = {}\n", self.muted);
+        config
+    }
+
+    /// Reads `key = value` lines, keeping the default for anything missing or unreadable.
+    pub fn from_config(config: &str) -> Self {
+        let mut settings = AudioSettings::default();
+        for line in config.lines() {
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => continue,
+            };
+
+            if key == "muted" {
+                settings.muted = value.parse().unwrap_or(settings.muted);
+            } else if let Some(volume) = Volume::ALL.into_iter().find(|volume| volume.key() == key) {
+                if let Ok(value) = value.parse() {
+                    settings.set(volume, value);
+                }
+            }
+        }
+        settings
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load() -> Self {
+        std::fs::read_to_string(SETTINGS_FILE)
+            .map(|config| AudioSettings::from_config(&config))
+            .unwrap_or_default()
+    }
+
+    // the browser has no files, so the config goes in local storage under the same name
+    #[cfg(target_arch = "wasm32")]
+    pub fn load() -> Self {
+        web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .and_then(|storage| storage.get_item(SETTINGS_FILE).ok().flatten())
+            .map(|config| AudioSettings::from_config(&config))
+            .unwrap_or_default()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self) {
+        if let Err(err) = std::fs::write(SETTINGS_FILE, self.to_config()) {
+            warn!("Couldn't save audio settings: {}", err);
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn save(&self) {
+        let storage = web_sys::window().and_then(|window| window.local_storage().ok().flatten());
+        let saved = storage.map_or(false, |storage| storage.set_item(SETTINGS_FILE, &self.to_config()).is_ok());
+        if !saved {
+            warn!("Couldn't save audio settings to local storage");
+        }
+    }
+}
+
+//==================================================================================================
+//                          Menu
+//==================================================================================================
+
+/// Opened and closed with O. Settings are saved as soon as they change.
+#[derive(Component)]
+pub struct AudioSettingsMenu;
+
+#[derive(Component)]
+pub struct VolumeSlider(Volume);
+
+#[derive(Component)]
+pub struct VolumeKnob(Volume);
+
+#[derive(Component)]
+pub struct VolumeLabel(Volume);
+
+#[derive(Component)]
+pub struct MuteToggle;
+
+#[derive(Component)]
+pub struct AudioSettingsTitle;
+
+fn slider_center(row: usize) -> Vec2 {
+    Vec2::new(40., 100. - ROW_SPACING * row as f32)
+}
+
+fn knob_x(value: f64) -> f32 {
+    (value as f32 - 0.5) * SLIDER_SIZE.x
+}
+
+fn volume_label(settings: &AudioSettings, localization: &Localization, volume: Volume) -> String {
+    format!("{} {}%", localization.text(volume.name()), (settings.get(volume) * 100.).round())
+}
+
+fn mute_label(settings: &AudioSettings, localization: &Localization) -> String {
+    format!("{}: {}", localization.text("Sound"), localization.text(if settings.muted { "Off" } else { "On" }))
+}
+
+fn spawn_audio_settings_menu(commands: &mut Commands, asset_server: &AssetServer, settings: &AudioSettings, localization: &Localization) {
+    let font = asset_server.load("fonts/pixel_font.ttf");
+    let text_style = TextStyle {
+        font: font.clone(),
+        font_size: 24.,
+        color: Color::WHITE,
+    };
+    let right_aligned = TextAlignment {
+        vertical: VerticalAlign::Center,
+        horizontal: HorizontalAlign::Right,
+    };
+
+    let title = commands.spawn_bundle(Text2dBundle {
+        text: Text::from_section(localization.text("Audio"), TextStyle {
+            font,
+            font_size: 40.,
+            color: Color::WHITE,
+        }).with_alignment(TextAlignment::CENTER),
+        transform: Transform::from_xyz(0., 170., 1.),
+        ..default()
+    })
+        .insert(AudioSettingsTitle)
+        .id();
+
+    let mut children = vec![title];
+    for (row, volume) in Volume::ALL.into_iter().enumerate() {
+        let center = slider_center(row);
+
+        let label = commands.spawn_bundle(Text2dBundle {
+            text: Text::from_section(volume_label(settings, localization, volume), text_style.clone()).with_alignment(right_aligned),
+            transform: Transform::from_xyz(center.x - SLIDER_SIZE.x / 2. - 20., center.y, 1.),
+            ..default()
+        })
+            .insert(VolumeLabel(volume))
+            .id();
+
+        let knob = commands.spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgb(1.0, 0.85, 0.3),
+                custom_size: Some(KNOB_SIZE),
+                ..default()
+            },
+            transform: Transform::from_xyz(knob_x(settings.get(volume)), 0., 1.),
+            ..default()
+        })
+            .insert(VolumeKnob(volume))
+            .id();
+
+        let slider = commands.spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgb(0.4, 0.4, 0.4),
+                custom_size: Some(SLIDER_SIZE),
+                ..default()
+            },
+            transform: Transform::from_xyz(center.x, center.y, 1.),
+            ..default()
+        })
+            .insert(VolumeSlider(volume))
+            .insert(Name::new(format!("{} Volume Slider", volume.name())))
+            .add_child(knob)
+            .id();
+
+        children.push(label);
+        children.push(slider);
+    }
+
+    let mute = commands.spawn_bundle(Text2dBundle {
+        text: Text::from_section(mute_label(settings, localization), text_style).with_alignment(TextAlignment::CENTER),
+        transform: Transform::from_xyz(0., slider_center(Volume::ALL.len()).y, 1.),
+        ..default()
+    })
+        .insert(MuteToggle)
+        .insert(Name::new("Mute Toggle"))
+        .id();
+    children.push(mute);
+
+    commands.spawn_bundle(SpriteBundle {
+        sprite: Sprite {
+            color: Color::rgba(0., 0., 0., 0.85),
+            custom_size: Some(Vec2::new(560., 420.)),
+            ..default()
+        },
+        transform: Transform::from_xyz(0., 0., MENU_LEVEL),
+        ..default()
+    })
+        .insert(AudioSettingsMenu)
+        .insert(ModalMenu)
+        .insert(Name::new("Audio Settings Menu"))
+        .push_children(&children);
+}
+
+//==================================================================================================
+//                          Systems
+//==================================================================================================
+
+pub fn toggle_audio_settings_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    keys: Res<Input<KeyCode>>,
+    ui_data: Res<UiData>,
+    settings: Res<AudioSettings>,
+    localization: Res<Localization>,
+    menu: Query<Entity, With<AudioSettingsMenu>>,
+) {
+    if !keys.just_pressed(KeyCode::O) || ui_data.view.search_focused {
+        return;
+    }
+
+    if menu.is_empty() {
+        spawn_audio_settings_menu(&mut commands, &asset_server, &settings, &localization);
+    } else {
+        for entity in menu.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub fn use_audio_settings_menu(
+    game_helper: Res<GameHelper>,
+    mouse: Res<Input<MouseButton>>,
+    mut settings: ResMut<AudioSettings>,
+    menu: Query<Entity, With<AudioSettingsMenu>>,
+    sliders: Query<(&GlobalTransform, &VolumeSlider)>,
+    mute: Query<&GlobalTransform, With<MuteToggle>>,
+) {
+    if !menu.iter().any(|menu| game_helper.is_top_menu(menu)) {
+        return;
+    }
+
+    let mouse_pos = game_helper.mouse_world_pos();
+
+    // sliders follow the mouse for as long as it is held down on them
+    if mouse.pressed(MouseButton::Left) {
+        for (transform, slider) in sliders.iter() {
+            let center = transform.translation().truncate();
+            let grab = Rect::from_center(center, Vec2::new(SLIDER_SIZE.x, SLIDER_GRAB_HEIGHT));
+            if !grab.is_within(mouse_pos) {
+                continue;
+            }
+
+            let value = ((mouse_pos.x - grab.x1) / SLIDER_SIZE.x) as f64;
+            let value = (value / SLIDER_STEP).round() * SLIDER_STEP;
+            if settings.get(slider.0) != value {
+                settings.set(slider.0, value);
+            }
+        }
+    }
+
+    if mouse.just_pressed(MouseButton::Left) {
+        let clicked_mute = mute.iter()
+            .any(|transform| Rect::from_center(transform.translation().truncate(), Vec2::new(200., 32.)).is_within(mouse_pos));
+        if clicked_mute {
+            settings.muted = !settings.muted;
+        }
+    }
+}
+
+pub fn render_audio_settings_menu(
+    settings: Res<AudioSettings>,
+    localization: Res<Localization>,
+    mut knobs: Query<(&mut Transform, &VolumeKnob)>,
+    mut labels: Query<(&mut Text, &VolumeLabel), Without<MuteToggle>>,
+    mut mute: Query<&mut Text, (With<MuteToggle>, Without<AudioSettingsTitle>)>,
+    mut title: Query<&mut Text, (With<AudioSettingsTitle>, Without<VolumeLabel>)>,
+) {
+    if !settings.is_changed() && !localization.is_changed() {
+        return;
+    }
+
+    for (mut transform, knob) in knobs.iter_mut() {
+        transform.translation.x = knob_x(settings.get(knob.0));
+    }
+    for (mut text, label) in labels.iter_mut() {
+        text.sections[0].value = volume_label(&settings, &localization, label.0);
+    }
+    for mut text in mute.iter_mut() {
+        text.sections[0].value = mute_label(&settings, &localization);
+    }
+    for mut text in title.iter_mut() {
+        text.sections[0].value = localization.text("Audio").to_string();
+    }
+}
+
+/// Saves once a change has settled, so dragging a slider doesn't write the file for every step.
+pub fn save_audio_settings(
+    settings: Res<AudioSettings>,
+    mouse: Res<Input<MouseButton>>,
+    mut unsaved: Local<bool>,
+) {
+    if settings.is_changed() && !settings.is_added() {
+        *unsaved = true;
+    }
+
+    if *unsaved && !mouse.pressed(MouseButton::Left) {
+        settings.save();
+        *unsaved = false;
+    }
+}
+
+/// Voices are all one loop at a time, so the channel volume can follow the settings directly.
+/// Music and sound effects pick their volume up from the settings as they play.
+pub fn apply_audio_settings(
+    settings: Res<AudioSettings>,
+    dialogue: Res<AudioChannel<DialogueChannel>>,
+) {
+    if settings.is_changed() {
+        dialogue.set_volume(VOICE_VOLUME * settings.scale(Volume::Voice));
+    }
+}
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use rand::Rng;
+use crate::audio::SfxChannel;
+use crate::audio::settings::{AudioSettings, Volume};
+use crate::ui::{CraftFailedEvent, CraftRepeatedEvent, CraftType, ElementCraftedEvent, ElementRewardEvent, LoadFurnaceEvent, LoadMixerEvent, LoadSlicerEvent};
+
+// how far the pitch can wander either way, so repeated sounds don't grate
+const PITCH_VARIATION: f64 = 0.08;
+
+/// Everything in the game that makes a sound.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sfx {
+    PickUp,
+    Drop,
+    Crafted(CraftType),
+    CraftFailed,
+    CraftRepeated,
+    ToolUnlocked,
+    Reward,
+    PageFlip,
+}
+
+impl Sfx {
+    /// The files to pick from, and how loud to play them.
+    pub fn sound(&self) -> (&'static [&'static str], f64) {
+        match self {
+            Sfx::PickUp => (&["sounds/sfx/pick_up.wav"], 0.3),
+            Sfx::Drop => (&["sounds/sfx/drop.wav"], 0.3),
+            Sfx::Crafted(CraftType::MIXER) => (&["sounds/sfx/mixer.wav"], 0.4),
+            Sfx::Crafted(CraftType::FURNACE) => (&["sounds/sfx/furnace.wav"], 0.4),
+            Sfx::Crafted(CraftType::SLICER) => (&["sounds/sfx/slicer.wav"], 0.4),
+            Sfx::CraftFailed => (&["sounds/sfx/fail_buzz.wav"], 0.35),
+            Sfx::CraftRepeated => (&["sounds/sfx/already_known.wav"], 0.2),
+            Sfx::ToolUnlocked => (&["sounds/sfx/tool_unlocked.wav"], 0.5),
+            Sfx::Reward => (&["sounds/sfx/reward.wav"], 0.4),
+            Sfx::PageFlip => (&["sounds/page-flip-01.mp3", "sounds/page-flip-02.mp3", "sounds/page-flip-03.mp3"], 0.4),
+        }
+    }
+}
+
+pub struct PlaySfxEvent(pub Sfx);
+
+//==================================================================================================
+//                          Systems
+//==================================================================================================
+
+pub fn play_sfx(
+    asset_server: Res<AssetServer>,
+    channel: Res<AudioChannel<SfxChannel>>,
+    settings: Res<AudioSettings>,
+    mut play_sfx_event: EventReader<PlaySfxEvent>,
+) {
+    let mut rng = rand::thread_rng();
+    for event in play_sfx_event.iter() {
+        let (files, volume) = event.0.sound();
+        let file = files[rng.gen_range(0..files.len())];
+        channel.play(asset_server.load(file))
+            .with_volume(volume * settings.scale(Volume::Sfx))
+            .with_playback_rate(rng.gen_range(1. - PITCH_VARIATION..=1. + PITCH_VARIATION));
+    }
+}
+
+/// Turns crafting, unlock and reward events into sounds.
+pub fn sfx_for_crafting(
+    mut element_crafted_event: EventReader<ElementCraftedEvent>,
+    mut craft_failed_event: EventReader<CraftFailedEvent>,
+    mut craft_repeated_event: EventReader<CraftRepeatedEvent>,
+    mut load_mixer_event: EventReader<LoadMixerEvent>,
+    mut load_furnace_event: EventReader<LoadFurnaceEvent>,
+    mut load_slicer_event: EventReader<LoadSlicerEvent>,
+    mut element_reward_event: EventReader<ElementRewardEvent>,
+    mut play_sfx_event: EventWriter<PlaySfxEvent>,
+) {
+    for event in element_crafted_event.iter() {
+        play_sfx_event.send(PlaySfxEvent(Sfx::Crafted(event.1)));
+    }
+    for _ in craft_failed_event.iter() {
+        play_sfx_event.send(PlaySfxEvent(Sfx::CraftFailed));
+    }
+    for _ in craft_repeated_event.iter() {
+        play_sfx_event.send(PlaySfxEvent(Sfx::CraftRepeated));
+    }
+
+    let unlocked = load_mixer_event.iter().count() + load_furnace_event.iter().count() + load_slicer_event.iter().count();
+    if unlocked > 0 {
+        play_sfx_event.send(PlaySfxEvent(Sfx::ToolUnlocked));
+    }
+    if element_reward_event.iter().count() > 0 {
+        play_sfx_event.send(PlaySfxEvent(Sfx::Reward));
+    }
+}
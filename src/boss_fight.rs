@@ -7,14 +7,28 @@ use bevy::utils::tracing::event;
 use bevy_inspector_egui::egui::{DragValue, Ui};
 use bevy_inspector_egui::{Context, Inspectable, RegisterInspectable};
 use bevy_prototype_debug_lines::DebugLines;
+use crate::accessibility::AccessibilitySettings;
+use crate::difficulty::Difficulty;
 use crate::element::Element;
+use crate::game::GameManager;
 use crate::GameHelper;
-use crate::helper::add_scaled_pixel_asset;
+use crate::helper::{add_scaled_pixel_asset, ModalMenu};
+use crate::locale::Localization;
 use crate::page::MovingTo;
-use crate::ui::{ElementCraftedEvent, Rect, Slot, ToolSlot, UiData};
+use crate::plating::{Grade, PlatingRecipe};
+use crate::focus::Focusable;
+use crate::ui::{Rect, Slot, ToolSlot, UiData};
 
 pub struct BossFightPlugin;
 
+/// Seconds left on the boss clock that send a `BossTimerThresholdEvent`.
+pub const CLOCK_THRESHOLDS : [u64; 3] = [300, 60, 10];
+
+const CLOCK_COLOR : Color = Color::rgb(0.57, 0.42, 0.28);
+const CLOCK_FLASH_COLOR : Color = Color::rgb(0.9, 0.15, 0.1);
+const CLOCK_FLASH_BELOW : f32 = 10.0;
+const CLOCK_FLASH_SECONDS : f32 = 0.5;
+
 impl Plugin for BossFightPlugin {
     fn build(&self, app: &mut App) {
         app
@@ -26,15 +40,24 @@ impl Plugin for BossFightPlugin {
             .add_event::<WinGameEvent>()
             .add_event::<LoseGameEvent>()
             .add_event::<CheckElementsEvent>()
+            .add_event::<StartBossPhaseEvent>()
+            .add_event::<PlateServedEvent>()
+            .add_event::<BossTimerThresholdEvent>()
+            .add_event::<AdjustBossTimeEvent>()
             .init_resource::<BossUIData>()
             //.add_system(test_system)
             .add_system(tick_clock)
+            .add_system(flash_clock.after(tick_clock))
             .add_system(on_click::<ToggleBossUIEvent>)
             .add_system(on_click::<CheckElementsEvent>)
             .add_system_to_stage(CoreStage::PostUpdate, setup_boss_fight)
             .add_system_to_stage(CoreStage::PostUpdate, on_toggle_timer)
+            .add_system_to_stage(CoreStage::PostUpdate, on_start_boss_phase)
+            .add_system_to_stage(CoreStage::PostUpdate, on_adjust_boss_time)
             .add_system_to_stage(CoreStage::PostUpdate, on_toggle_boss_ui)
             .add_system_to_stage(CoreStage::PostUpdate, on_check_elements)
+            .add_system_to_stage(CoreStage::PostUpdate, on_win_game)
+            .add_system(dismiss_win_screen)
         ;
     }
 }
@@ -55,6 +78,9 @@ pub struct BossDoneButton;
 #[derive(Component)]
 pub struct TimeCounter;
 
+#[derive(Component)]
+pub struct PlatingReport;
+
 #[derive(Component, Clone)]
 pub struct Clickable<T> where T : Sized + Default {
     pub rect : Rect,
@@ -93,7 +119,70 @@ impl <T> Inspectable for Clickable<T> where T : Sized + Default {
 #[derive(Component)]
 pub struct BossTimer {
     timer : Timer,
-    is_ticking : bool
+    is_ticking : bool,
+    // what was left on the previous tick, to tell when a threshold gets crossed
+    last_remaining : f32,
+    has_expired : bool
+}
+
+impl BossTimer {
+    pub fn new(seconds : u64) -> Self {
+        BossTimer {
+            timer : Timer::new(Duration::from_secs(seconds), false),
+            is_ticking : false,
+            last_remaining : seconds as f32,
+            has_expired : false
+        }
+    }
+
+    pub fn remaining(&self) -> f32 {
+        (self.timer.duration() - self.timer.elapsed()).as_secs_f32()
+    }
+
+    /// Puts time back on the clock, or takes it off for negative `seconds`. Never goes past the
+    /// full length or below zero.
+    pub fn add_time(&mut self, seconds : f32) {
+        let elapsed = (self.timer.elapsed_secs() - seconds).clamp(0.0, self.timer.duration().as_secs_f32());
+        self.timer.set_elapsed(Duration::from_secs_f32(elapsed));
+    }
+
+    /// The share of the clock still left, from 1 at the start down to 0.
+    pub fn time_left(&self) -> f32 {
+        1.0 - self.timer.percent()
+    }
+
+    pub fn is_ticking(&self) -> bool {
+        self.is_ticking
+    }
+
+    /// Runs the clock down by `delta` if it is ticking. Gives back the thresholds it went past and
+    /// whether time just ran out, which only happens once.
+    pub fn tick(&mut self, delta : Duration) -> (Vec<u64>, bool) {
+        if self.is_ticking {
+            self.timer.tick(delta);
+        }
+
+        let remaining = self.remaining();
+        let crossed = CLOCK_THRESHOLDS.iter()
+            .copied()
+            .filter(|threshold| self.last_remaining > *threshold as f32 && remaining <= *threshold as f32)
+            .collect();
+        self.last_remaining = remaining;
+
+        let expired = self.timer.finished() && !self.has_expired;
+        if expired {
+            self.has_expired = true;
+        }
+        (crossed, expired)
+    }
+}
+
+#[derive(Component)]
+pub struct ClockFlash(Timer);
+
+#[derive(Component)]
+pub struct WinScreen {
+    timer : Timer
 }
 
 //=================================================================================================
@@ -140,20 +229,42 @@ pub struct LoseGameEvent;
 #[derive(Default, Debug)]
 pub struct WinGameEvent;
 
+/// Clears the plate and starts the clock for the next course. The seconds are for normal
+/// difficulty and get scaled.
+#[derive(Debug)]
+pub struct StartBossPhaseEvent {
+    pub plating : PlatingRecipe,
+    pub seconds : u64
+}
+
+/// The boss clock just went down to this many seconds, one of `CLOCK_THRESHOLDS`.
+#[derive(Debug, Clone, Copy)]
+pub struct BossTimerThresholdEvent(pub u64);
+
+/// Adds seconds to the boss clock, or takes them off when negative.
+#[derive(Debug)]
+pub struct AdjustBossTimeEvent(pub f32);
+
+/// A complete plate was handed to the boss.
+#[derive(Debug)]
+pub struct PlateServedEvent(pub Element);
+
 //=================================================================================================
 //                              Resources
 //=================================================================================================
 
 pub struct BossUIData {
     boss_ui_id : Option<Entity>,
-    is_out : bool
+    is_out : bool,
+    pub plating : PlatingRecipe,
 }
 
 impl Default for BossUIData {
     fn default() -> Self {
         BossUIData {
             boss_ui_id : None,
-            is_out : false
+            is_out : false,
+            plating : PlatingRecipe::RAMEN,
         }
     }
 }
@@ -167,7 +278,7 @@ pub fn setup_boss_fight (
     asset_server : Res<AssetServer>,
     mut setup_boss_fight : EventReader<SetupBossFightEvent>,
     mut ui : ResMut<UiData>,
-    mut boss_ui_data : ResMut<BossUIData>
+    mut boss_ui_data : ResMut<BossUIData>,
 ) {
     if !setup_boss_fight.is_empty() && boss_ui_data.boss_ui_id.is_none() {
         let parent = add_scaled_pixel_asset(&mut commands, &asset_server, "sprites/boss_fight_ui.png", 40, 88, SpriteBundle {
@@ -184,7 +295,7 @@ pub fn setup_boss_fight (
         }).insert(Clickable {
             rect : Rect::new(-38.0, 38.0, 38.0, -38.0),
             event : ToggleBossUIEvent
-        }).insert(BossToggleButton).id();
+        }).insert(BossToggleButton).insert(Focusable::sized(Vec2::splat(76.0))).id();
 
         let done_button = commands.spawn()
             .insert(Transform::from_xyz(0.0, -316.0, 1.0))
@@ -194,13 +305,16 @@ pub fn setup_boss_fight (
                 event : CheckElementsEvent
             })
             .insert(BossDoneButton)
+            .insert(Focusable::sized(Vec2::new(272.0, 56.0)))
             .id();
 
         let text_style = TextStyle {
             font: asset_server.load("fonts/pixel_font.ttf"),
             font_size: 65.,
-            color: Color::rgb(0.57, 0.42, 0.28),
+            color: CLOCK_COLOR,
         };
+        // stopped and empty, each course sets the clock when it starts
+        let boss_timer = BossTimer::new(0);
 
         let clock_alignment = TextAlignment {
             vertical: VerticalAlign::Center,
@@ -208,18 +322,29 @@ pub fn setup_boss_fight (
         };
 
         let clock_text = commands.spawn_bundle(Text2dBundle {
-            text : Text::from_section(" 9 59", text_style).with_alignment(clock_alignment),
+            text : Text::from_section(format_clock(boss_timer.remaining()), text_style).with_alignment(clock_alignment),
             transform : Transform::from_xyz(0.0, 296.0, 1.0),
             text_2d_bounds : Text2dBounds {
                 size : Vec2::new(236.0, 86.0)
             },
             ..default()
-        }).insert(BossTimer {
-            is_ticking : false,
-            timer : Timer::new(Duration::from_secs(600), false)
-        }).id();
+        }).insert(boss_timer).insert(ClockFlash(Timer::from_seconds(CLOCK_FLASH_SECONDS, true))).id();
+
+        // what is still missing from the plate, shown to the left of the menu
+        let report_text = commands.spawn_bundle(Text2dBundle {
+            text : Text::from_section("", TextStyle {
+                font: asset_server.load("fonts/pixel_font.ttf"),
+                font_size: 24.,
+                color: Color::WHITE,
+            }).with_alignment(TextAlignment::TOP_RIGHT),
+            transform : Transform::from_xyz(-176.0, 168.0, 1.0),
+            text_2d_bounds : Text2dBounds {
+                size : Vec2::new(260.0, 400.0)
+            },
+            ..default()
+        }).insert(PlatingReport).id();
 
-        commands.entity(parent.clone()).push_children(&[click, clock_text, done_button]);
+        commands.entity(parent).push_children(&[click, clock_text, done_button, report_text]);
 
         add_slot_array(&mut commands, -64.0, 168.0, 2, 4, 128.0, &mut ui.amount_of_slots_indices, &parent);
 
@@ -243,6 +368,7 @@ fn add_slot_array(commands: &mut Commands, x : f32, y : f32, width : u32, height
                 .insert(Slot::with_index_changable(starting_slot.clone()))
                 .insert(BossUiSlot)
                 .insert(ToolSlot)
+                .insert(Focusable::default())
                 .id();
             commands.entity(parent.clone()).add_child(slot);
             *starting_slot += 1;
@@ -295,42 +421,71 @@ pub fn on_toggle_timer (
     }
 }
 
+pub fn on_start_boss_phase (
+    mut boss_slots : Query<&mut Slot, With<BossUiSlot>>,
+    mut boss_ui_data : ResMut<BossUIData>,
+    difficulty : Res<Difficulty>,
+    mut timer : Query<&mut BossTimer>,
+    mut report : Query<&mut Text, With<PlatingReport>>,
+    mut start_boss_phase_event : EventReader<StartBossPhaseEvent>,
+    mut toggle_boss_ui_event : EventWriter<ToggleBossUIEvent>
+) {
+    for event in start_boss_phase_event.iter() {
+        boss_ui_data.plating = event.plating.clone();
+
+        for mut slot in boss_slots.iter_mut() {
+            slot.element = None;
+        }
+
+        if let Ok(mut text) = report.get_single_mut() {
+            text.sections[0].value.clear();
+        }
+
+        if let Ok(mut timer) = timer.get_single_mut() {
+            *timer = BossTimer::new(difficulty.boss_seconds(event.seconds));
+            timer.is_ticking = true;
+        }
+
+        if !boss_ui_data.is_out {
+            toggle_boss_ui_event.send(ToggleBossUIEvent)
+        }
+    }
+}
+
 pub fn on_check_elements (
     boss_slots : Query<&Slot, With<BossUiSlot>>,
+    boss_ui_data : Res<BossUIData>,
+    difficulty : Res<Difficulty>,
+    localization : Res<Localization>,
+    mut game : ResMut<GameManager>,
+    mut timer : Query<&mut BossTimer>,
+    mut report : Query<&mut Text, With<PlatingReport>>,
     mut on_check_elements : EventReader<CheckElementsEvent>,
-    mut element_crafted_event : EventWriter<ElementCraftedEvent>,
+    mut plate_served_event : EventWriter<PlateServedEvent>,
     mut toggle_boss_ui_event : EventWriter<ToggleBossUIEvent>,
-    mut toggle_timer_event : EventWriter<ToggleBossTimerEvent>
+    mut adjust_time_event : EventWriter<AdjustBossTimeEvent>
 ) {
     if !on_check_elements.is_empty() {
+        let plated : Vec<Element> = boss_slots.iter().filter_map(|slot| slot.element.clone()).collect();
+        let result = boss_ui_data.plating.evaluate(&plated);
 
-        let mut has_pepper_flakes = false;
-        let mut has_chopsticks = false;
-        let mut has_broth = false;
-        let mut has_hard_boiled_eggs = false;
-        let mut has_chashu = false;
-        let mut has_noodles = false;
-        let mut has_dried_seaweed = false;
-        let mut has_ice_cube = false;
-
-
-        for slot in boss_slots.iter() {
-            if let Some(element) = &slot.element {
-                if *element == Element::PEPPER_FLAKES { has_pepper_flakes = true }
-                if *element == Element::BONE_CHOPSTICKS { has_chopsticks = true }
-                if *element == Element::PORK_BROTH { has_broth = true }
-                if *element == Element::HARD_BOILED_EGG { has_hard_boiled_eggs = true }
-                if *element == Element::CHASHU { has_chashu = true }
-                if *element == Element::RAMEN_NOODLES { has_noodles = true }
-                if *element == Element::DRIED_SEAWEED { has_dried_seaweed = true }
-                if *element == Element::GLACIER_ICE { has_ice_cube = true }
-            }
+        if let Ok(mut text) = report.get_single_mut() {
+            text.sections[0].value = result.report(&localization);
         }
 
-        if has_pepper_flakes && has_chopsticks && has_broth && has_hard_boiled_eggs && has_chashu && has_noodles && has_dried_seaweed && has_ice_cube {
-            element_crafted_event.send(ElementCraftedEvent(Element::RAMEN));
-            toggle_boss_ui_event.send(ToggleBossUIEvent);
-            toggle_timer_event.send(ToggleBossTimerEvent)
+        if result.is_complete() {
+            let time_left = timer.get_single().map(|timer| timer.time_left()).unwrap_or(0.0);
+            let dish = boss_ui_data.plating.dish.clone();
+            game.boss_scores.push((dish.clone(), result.grade(time_left)));
+
+            if let Ok(mut timer) = timer.get_single_mut() {
+                timer.is_ticking = false;
+            }
+            plate_served_event.send(PlateServedEvent(dish));
+            toggle_boss_ui_event.send(ToggleBossUIEvent)
+        } else if difficulty.wrong_plate_penalty() > 0 {
+            // sending back a plate that isn't ready costs time on the harder settings
+            adjust_time_event.send(AdjustBossTimeEvent(-(difficulty.wrong_plate_penalty() as f32)));
         }
 
         on_check_elements.clear()
@@ -363,35 +518,57 @@ pub fn test_system (
 pub fn tick_clock (
     mut clock : Query<(&mut BossTimer, &mut Text)>,
     time : Res<Time>,
-    mut lose_game_event : EventWriter<LoseGameEvent>
+    mut lose_game_event : EventWriter<LoseGameEvent>,
+    mut threshold_event : EventWriter<BossTimerThresholdEvent>
 ) {
     let clock = clock.get_single_mut();
 
     if let Ok((mut timer, mut text)) = clock {
-        let duration = timer.timer.elapsed();
-        let seconds = 60 - (duration.as_secs() % 60);
-        let mut minutes = 9 - ((duration.as_secs() / 60) % 60);
-        let is_60 = seconds == 60;
-        let text_value = if is_60 {
-            minutes += 1;
-            format!("{minutes:0>2} 00")
-        } else {
-            format!("{minutes:0>2} {seconds:0>2}")
-        };
+        let (crossed, expired) = timer.tick(time.delta());
+        for threshold in crossed {
+            threshold_event.send(BossTimerThresholdEvent(threshold));
+        }
 
-        text.sections.get_mut(0).unwrap().value = text_value;
+        text.sections.get_mut(0).unwrap().value = format_clock(timer.remaining());
 
-        if timer.timer.finished() {
-            println!("YOU LOOSE");
+        if expired {
             lose_game_event.send(LoseGameEvent);
         }
+    }
+}
 
-        if timer.is_ticking {
-            timer.timer.tick(time.delta());
+pub fn on_adjust_boss_time (
+    mut timer : Query<&mut BossTimer>,
+    mut adjust_time_event : EventReader<AdjustBossTimeEvent>
+) {
+    for event in adjust_time_event.iter() {
+        if let Ok(mut timer) = timer.get_single_mut() {
+            timer.add_time(event.0);
         }
     }
 }
 
+/// Blinks the clock red once time is nearly up.
+pub fn flash_clock (
+    time : Res<Time>,
+    accessibility : Res<AccessibilitySettings>,
+    mut clock : Query<(&BossTimer, &mut ClockFlash, &mut Text)>
+) {
+    for (timer, mut flash, mut text) in clock.iter_mut() {
+        flash.0.tick(time.delta());
+
+        let urgent = timer.is_ticking && timer.remaining() <= CLOCK_FLASH_BELOW;
+        let lit = accessibility.reduced_motion || flash.0.percent() < 0.5;
+        text.sections[0].style.color = if urgent && lit { CLOCK_FLASH_COLOR } else { CLOCK_COLOR };
+    }
+}
+
+/// Minutes and seconds left, rounded up so the clock only reads "00 00" once time is up.
+fn format_clock(remaining : f32) -> String {
+    let remaining = remaining.max(0.0).ceil() as u64;
+    format!("{:0>2} {:0>2}", remaining / 60, remaining % 60)
+}
+
 pub fn on_click<T> (
     mut lines : ResMut<DebugLines>,
     clickables : Query<(&Clickable<T>, &GlobalTransform), With<Transform>>,
@@ -402,8 +579,162 @@ pub fn on_click<T> (
     for (clickable, transform) in clickables.iter() {
         let trans = transform.translation();
         //clickable.rect.draw_rect_with_offset(&mut lines, Color::GREEN, trans.truncate());
-        if clicks.just_pressed(MouseButton::Left) && clickable.rect.is_within_with_offset(game_helper.mouse_world_pos(), trans.truncate()) {
+        if clicks.just_pressed(MouseButton::Left) && !game_helper.is_menu_open() && clickable.rect.is_within_with_offset(game_helper.mouse_world_pos(), trans.truncate()) {
             event_writer.send(T::default())
         }
     }
-}
\ No newline at end of file
+}
+
+//=================================================================================================
+//                              Win Screen
+//=================================================================================================
+
+const WIN_SCREEN_SECONDS : f32 = 8.0;
+// clicks right after it shows up are still meant for the dialogue
+const WIN_SCREEN_MIN_SECONDS : f32 = 1.0;
+
+pub fn on_win_game (
+    mut commands : Commands,
+    asset_server : Res<AssetServer>,
+    game : Res<GameManager>,
+    difficulty : Res<Difficulty>,
+    localization : Res<Localization>,
+    win_game_event : EventReader<WinGameEvent>
+) {
+    if win_game_event.is_empty() {
+        return;
+    }
+    win_game_event.clear();
+
+    if game.boss_scores.is_empty() {
+        return;
+    }
+
+    // the run is graded on the average over every course, each course gets its own breakdown
+    let average = game.boss_scores.iter().map(|(_, score)| score.score).sum::<i32>() / game.boss_scores.len() as i32;
+    let mut summary : Vec<String> = game.boss_scores.iter()
+        .map(|(dish, score)| format!("{}: {}\n{}", localization.element_name(dish), score.grade().letter(), score.breakdown(&localization)))
+        .collect();
+    summary.push(format!("{}: {}", localization.text("Difficulty"), localization.text(difficulty.name())));
+
+    let font = asset_server.load("fonts/pixel_font.ttf");
+    let grade_text = commands.spawn_bundle(Text2dBundle {
+        text : Text::from_section(Grade::from_score(average).letter(), TextStyle {
+            font : font.clone(),
+            font_size : 160.,
+            color : Color::rgb(1.0, 0.85, 0.3),
+        }).with_alignment(TextAlignment::CENTER),
+        transform : Transform::from_xyz(0.0, 230.0, 1.0),
+        ..default()
+    }).id();
+
+    let breakdown_text = commands.spawn_bundle(Text2dBundle {
+        text : Text::from_section(summary.join("\n\n"), TextStyle {
+            font,
+            font_size : 22.,
+            color : Color::WHITE,
+        }).with_alignment(TextAlignment::TOP_CENTER),
+        transform : Transform::from_xyz(0.0, 150.0, 1.0),
+        text_2d_bounds : Text2dBounds {
+            size : Vec2::new(480.0, 460.0)
+        },
+        ..default()
+    }).id();
+
+    commands.spawn_bundle(SpriteBundle {
+        sprite : Sprite {
+            color : Color::rgba(0.0, 0.0, 0.0, 0.8),
+            custom_size : Some(Vec2::new(560.0, 660.0)),
+            ..default()
+        },
+        transform : Transform::from_xyz(0.0, 0.0, 60.0),
+        ..default()
+    })
+        .insert(WinScreen {
+            timer : Timer::from_seconds(WIN_SCREEN_SECONDS, false)
+        })
+        .insert(ModalMenu)
+        .insert(Name::new("Win Screen"))
+        .push_children(&[grade_text, breakdown_text]);
+}
+
+pub fn dismiss_win_screen (
+    mut commands : Commands,
+    time : Res<Time>,
+    game_helper : Res<GameHelper>,
+    clicks : Res<Input<MouseButton>>,
+    mut screens : Query<(Entity, &mut WinScreen)>
+) {
+    for (entity, mut screen) in screens.iter_mut() {
+        screen.timer.tick(time.delta());
+        let clicked_away = clicks.just_pressed(MouseButton::Left) && game_helper.is_top_menu(entity) && screen.timer.elapsed_secs() > WIN_SCREEN_MIN_SECONDS;
+        if screen.timer.finished() || clicked_away {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticking(seconds : u64) -> BossTimer {
+        let mut timer = BossTimer::new(seconds);
+        timer.is_ticking = true;
+        timer
+    }
+
+    #[test]
+    fn format_clock_rounds_up_to_the_second() {
+        assert_eq!(format_clock(600.0), "10 00");
+        assert_eq!(format_clock(599.5), "10 00");
+        assert_eq!(format_clock(599.0), "09 59");
+        assert_eq!(format_clock(60.0), "01 00");
+        assert_eq!(format_clock(59.0), "00 59");
+        assert_eq!(format_clock(0.2), "00 01");
+        assert_eq!(format_clock(0.0), "00 00");
+        assert_eq!(format_clock(-3.0), "00 00");
+    }
+
+    #[test]
+    fn thresholds_fire_once_as_the_clock_passes_them() {
+        let mut timer = ticking(400);
+        let mut crossed = Vec::new();
+        let mut expired = 0;
+        for _ in 0..400 {
+            let (thresholds, has_expired) = timer.tick(Duration::from_secs(1));
+            crossed.extend(thresholds);
+            expired += has_expired as u32;
+        }
+        assert_eq!(crossed, vec![300, 60, 10]);
+        assert_eq!(expired, 1);
+
+        // nothing more once time is up
+        assert_eq!(timer.tick(Duration::from_secs(1)), (Vec::new(), false));
+    }
+
+    #[test]
+    fn one_long_tick_passes_every_threshold_at_once() {
+        let mut timer = ticking(301);
+        assert_eq!(timer.tick(Duration::from_secs(301)), (vec![300, 60, 10], true));
+    }
+
+    #[test]
+    fn stopped_clock_never_fires() {
+        let mut timer = BossTimer::new(0);
+        assert_eq!(timer.tick(Duration::from_secs(5)), (Vec::new(), false));
+
+        let mut timer = BossTimer::new(20);
+        assert_eq!(timer.tick(Duration::from_secs(15)), (Vec::new(), false));
+        assert_eq!(timer.remaining(), 20.0);
+    }
+
+    #[test]
+    fn adding_time_back_lets_a_threshold_fire_again() {
+        let mut timer = ticking(70);
+        assert_eq!(timer.tick(Duration::from_secs(10)).0, vec![60]);
+        timer.add_time(5.0);
+        assert_eq!(timer.tick(Duration::from_secs(0)).0, Vec::<u64>::new());
+        assert_eq!(timer.tick(Duration::from_secs(5)).0, vec![60]);
+    }
+}
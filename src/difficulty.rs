@@ -0,0 +1,233 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use crate::focus::Focusable;
+use crate::GameHelper;
+use crate::helper::ModalMenu;
+use crate::locale::{ChangeLanguageEvent, Localization};
+use crate::ui::Rect;
+
+const MENU_LEVEL: f32 = 80.;
+const OPTION_SIZE: Vec2 = Vec2::new(440., 56.);
+const OPTION_SPACING: f32 = 72.;
+
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Difficulty>()
+            .add_startup_system(spawn_difficulty_menu)
+            .add_system(choose_difficulty)
+            .add_system(render_difficulty_menu);
+    }
+}
+
+/// How hard the boss fight is. Picked once when the game starts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Chef,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard, Difficulty::Chef];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+            Difficulty::Chef => "Chef",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Extra time, hints and cookbook",
+            Difficulty::Normal => "Hints and cookbook",
+            Difficulty::Hard => "Less time, no hints, wrong plates cost time",
+            Difficulty::Chef => "Half the time, no hints or cookbook",
+        }
+    }
+
+    /// How long the boss gives you for a course that takes `seconds` on normal.
+    pub fn boss_seconds(&self, seconds: u64) -> u64 {
+        match self {
+            Difficulty::Easy => seconds * 3 / 2,
+            Difficulty::Normal => seconds,
+            Difficulty::Hard => seconds * 7 / 10,
+            Difficulty::Chef => seconds / 2,
+        }
+    }
+
+    /// Whether failed crafts still get a hint while the boss clock is running.
+    pub fn allows_hints(&self) -> bool {
+        matches!(self, Difficulty::Easy | Difficulty::Normal)
+    }
+
+    /// Whether element pages can be opened in the cookbook while the boss clock is running.
+    pub fn allows_cookbook(&self) -> bool {
+        *self != Difficulty::Chef
+    }
+
+    /// Seconds taken off the boss clock for each plate sent back.
+    pub fn wrong_plate_penalty(&self) -> u64 {
+        match self {
+            Difficulty::Easy | Difficulty::Normal => 0,
+            Difficulty::Hard => 30,
+            Difficulty::Chef => 60,
+        }
+    }
+}
+
+//==================================================================================================
+//                          Menu
+//==================================================================================================
+
+/// The menu shown at the start of the game. The story waits while it is up.
+#[derive(Component)]
+pub struct DifficultyMenu;
+
+#[derive(Component)]
+pub struct DifficultyTitle;
+
+#[derive(Component)]
+pub struct DifficultyOption(Difficulty);
+
+/// Cycles through the languages when clicked.
+#[derive(Component)]
+pub struct LanguageOption;
+
+fn spawn_difficulty_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/pixel_font.ttf");
+
+    let title = commands.spawn_bundle(Text2dBundle {
+        text: Text::from_section("Choose a difficulty", TextStyle {
+            font: font.clone(),
+            font_size: 48.,
+            color: Color::WHITE,
+        }).with_alignment(TextAlignment::CENTER),
+        transform: Transform::from_xyz(0., 180., 1.),
+        ..default()
+    })
+        .insert(DifficultyTitle)
+        .id();
+
+    let mut children = vec![title];
+    for (i, difficulty) in Difficulty::ALL.into_iter().enumerate() {
+        let option = commands.spawn_bundle(Text2dBundle {
+            text: Text::from_sections([
+                TextSection::new(format!("{}\n", difficulty.name()), TextStyle {
+                    font: font.clone(),
+                    font_size: 36.,
+                    color: Color::rgb(1.0, 0.85, 0.3),
+                }),
+                TextSection::new(difficulty.description(), TextStyle {
+                    font: font.clone(),
+                    font_size: 20.,
+                    color: Color::WHITE,
+                }),
+            ]).with_alignment(TextAlignment::CENTER),
+            transform: Transform::from_xyz(0., 90. - OPTION_SPACING * i as f32, 1.),
+            ..default()
+        })
+            .insert(DifficultyOption(difficulty))
+            .insert(Focusable::sized(OPTION_SIZE))
+            .insert(Name::new(format!("{} Difficulty", difficulty.name())))
+            .id();
+        children.push(option);
+    }
+
+    let language = commands.spawn_bundle(Text2dBundle {
+        text: Text::from_section("", TextStyle {
+            font: font.clone(),
+            font_size: 24.,
+            color: Color::rgb(0.7, 0.7, 0.7),
+        }).with_alignment(TextAlignment::CENTER),
+        transform: Transform::from_xyz(0., 90. - OPTION_SPACING * Difficulty::ALL.len() as f32, 1.),
+        ..default()
+    })
+        .insert(LanguageOption)
+        .insert(Focusable::sized(OPTION_SIZE))
+        .insert(Name::new("Language Option"))
+        .id();
+    children.push(language);
+
+    commands.spawn_bundle(SpriteBundle {
+        sprite: Sprite {
+            color: Color::rgba(0., 0., 0., 0.85),
+            custom_size: Some(Vec2::new(560., 520.)),
+            ..default()
+        },
+        transform: Transform::from_xyz(0., 0., MENU_LEVEL),
+        ..default()
+    })
+        .insert(DifficultyMenu)
+        .insert(ModalMenu)
+        .insert(Name::new("Difficulty Menu"))
+        .push_children(&children);
+}
+
+#[derive(SystemParam)]
+pub struct DifficultyMenuQueries<'w, 's> {
+    menu: Query<'w, 's, Entity, With<DifficultyMenu>>,
+    options: Query<'w, 's, (&'static GlobalTransform, &'static DifficultyOption)>,
+    language_options: Query<'w, 's, &'static GlobalTransform, With<LanguageOption>>,
+}
+
+fn choose_difficulty(
+    mut commands: Commands,
+    mut difficulty: ResMut<Difficulty>,
+    game_helper: Res<GameHelper>,
+    mouse: Res<Input<MouseButton>>,
+    localization: Res<Localization>,
+    menu: DifficultyMenuQueries,
+    mut change_language_event: EventWriter<ChangeLanguageEvent>,
+) {
+    if !mouse.just_pressed(MouseButton::Left) || !menu.menu.iter().any(|menu| game_helper.is_top_menu(menu)) {
+        return;
+    }
+
+    let clicked = |transform: &GlobalTransform| Rect::from_center(transform.translation().truncate(), OPTION_SIZE).is_within(game_helper.mouse_world_pos());
+    if menu.language_options.iter().any(clicked) {
+        change_language_event.send(ChangeLanguageEvent(localization.language().next()));
+        return;
+    }
+
+    let picked = menu.options.iter()
+        .find(|(transform, _)| clicked(transform));
+
+    if let Some((_, option)) = picked {
+        *difficulty = option.0;
+        for entity in menu.menu.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+type LanguageOptionText<'w, 's> = Query<'w, 's, &'static mut Text, (With<LanguageOption>, Without<DifficultyTitle>, Without<DifficultyOption>)>;
+
+fn render_difficulty_menu(
+    localization: Res<Localization>,
+    mut title: Query<&mut Text, With<DifficultyTitle>>,
+    mut options: Query<(&mut Text, &DifficultyOption), Without<DifficultyTitle>>,
+    mut language_options: LanguageOptionText,
+) {
+    if !localization.is_changed() {
+        return;
+    }
+
+    for mut text in title.iter_mut() {
+        text.sections[0].value = localization.text("Choose a difficulty").to_string();
+    }
+    for (mut text, option) in options.iter_mut() {
+        text.sections[0].value = format!("{}\n", localization.text(option.0.name()));
+        text.sections[1].value = localization.text(option.0.description()).to_string();
+    }
+    for mut text in language_options.iter_mut() {
+        text.sections[0].value = format!("{}: {}", localization.text("Language"), localization.language().name());
+    }
+}
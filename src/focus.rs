@@ -0,0 +1,261 @@
+use std::marker::PhantomData;
+use bevy::ecs::system::SystemParam;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::window::CursorMoved;
+use crate::helper::{GameHelper, PointerUpdate, VIRTUAL_SIZE};
+use crate::page::{PageNextEvent, PagePrevEvent};
+use crate::ui::UiData;
+
+const STICK_THRESHOLD: f32 = 0.5;
+const CURSOR_PADDING: f32 = 16.;
+const CURSOR_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.35);
+const CURSOR_LEVEL: f32 = 90.;
+
+pub struct FocusPlugin;
+
+impl Plugin for FocusPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Focus>()
+            .add_startup_system(spawn_focus_cursor)
+            .add_system_to_stage(CoreStage::PreUpdate, navigate_focus.after(InputSystem).after(PointerUpdate))
+            .add_system(render_focus_cursor)
+            .add_system_to_stage(CoreStage::PostUpdate, release_empty_clicks);
+    }
+}
+
+/// Something the focus cursor can land on. Without a size, it is taken from the sprite.
+#[derive(Component, Default)]
+pub struct Focusable {
+    pub size: Option<Vec2>,
+}
+
+impl Focusable {
+    pub fn sized(size: Vec2) -> Self {
+        Focusable { size: Some(size) }
+    }
+}
+
+/// The keyboard and gamepad cursor. While it is active it stands in for the mouse, so everything
+/// that reads the pointer and mouse buttons works the same for it.
+#[derive(Default)]
+pub struct Focus {
+    current: Option<Entity>,
+    active: bool,
+    // a click that picked nothing up is let go of on the next frame
+    release_next: Vec<MouseButton>,
+    last_stick: Vec2,
+}
+
+impl Focus {
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+}
+
+fn focus_rect(transform: &GlobalTransform, focusable: &Focusable, sprite: Option<&Sprite>, atlas_sprite: Option<&TextureAtlasSprite>) -> Option<(Vec2, Vec2)> {
+    let size = focusable.size
+        .or_else(|| sprite.and_then(|sprite| sprite.custom_size))
+        .or_else(|| atlas_sprite.and_then(|sprite| sprite.custom_size))?;
+    let pos = transform.translation().truncate();
+    // anything further out is parked off screen
+    let on_screen = pos.x.abs() < VIRTUAL_SIZE.x / 2. && pos.y.abs() < VIRTUAL_SIZE.y / 2.;
+    on_screen.then_some((pos, size))
+}
+
+/// The closest target roughly in `direction`, favouring ones straight ahead over ones off to the side.
+fn next_in_direction(from: Vec2, direction: Vec2, targets: &[(Entity, Vec2)], current: Option<Entity>) -> Option<Entity> {
+    targets.iter()
+        .filter(|(entity, _)| Some(*entity) != current)
+        .filter_map(|(entity, pos)| {
+            let offset = *pos - from;
+            let along = offset.dot(direction);
+            let across = offset.perp_dot(direction).abs();
+            (along > 1.).then_some((*entity, along + across * 2.))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity)
+}
+
+#[derive(SystemParam)]
+pub struct NavigationInput<'w, 's> {
+    keys: Res<'w, Input<KeyCode>>,
+    gamepads: Res<'w, Gamepads>,
+    buttons: Res<'w, Input<GamepadButton>>,
+    axes: Res<'w, Axis<GamepadAxis>>,
+    #[system_param(ignore)]
+    _marker: PhantomData<&'s ()>,
+}
+
+impl<'w, 's> NavigationInput<'w, 's> {
+    fn pad_pressed(&self, pad: GamepadButtonType) -> bool {
+        self.gamepads.iter().any(|gamepad| self.buttons.just_pressed(GamepadButton::new(*gamepad, pad)))
+    }
+
+    /// True on the frame the key or the pad button goes down.
+    fn pressed(&self, key: KeyCode, pad: GamepadButtonType) -> bool {
+        self.keys.just_pressed(key) || self.pad_pressed(pad)
+    }
+}
+
+fn read_direction(input: &NavigationInput, focus: &mut Focus) -> Option<Vec2> {
+    let pressed = |key: KeyCode, pad: GamepadButtonType| input.pressed(key, pad);
+
+    let mut direction = Vec2::ZERO;
+    if pressed(KeyCode::Up, GamepadButtonType::DPadUp) { direction.y += 1.; }
+    if pressed(KeyCode::Down, GamepadButtonType::DPadDown) { direction.y -= 1.; }
+    if pressed(KeyCode::Left, GamepadButtonType::DPadLeft) { direction.x -= 1.; }
+    if pressed(KeyCode::Right, GamepadButtonType::DPadRight) { direction.x += 1.; }
+
+    // the stick only counts once each time it is pushed past the threshold
+    let stick = input.gamepads.iter()
+        .map(|gamepad| Vec2::new(
+            input.axes.get(GamepadAxis::new(*gamepad, GamepadAxisType::LeftStickX)).unwrap_or(0.),
+            input.axes.get(GamepadAxis::new(*gamepad, GamepadAxisType::LeftStickY)).unwrap_or(0.),
+        ))
+        .find(|stick| stick.length() > STICK_THRESHOLD)
+        .unwrap_or(Vec2::ZERO);
+    if stick != Vec2::ZERO && focus.last_stick == Vec2::ZERO {
+        direction = stick;
+    }
+    focus.last_stick = stick;
+
+    (direction != Vec2::ZERO).then(|| direction.normalize())
+}
+
+//==================================================================================================
+//                          Systems
+//==================================================================================================
+
+pub fn navigate_focus(
+    mut focus: ResMut<Focus>,
+    mut game_helper: ResMut<GameHelper>,
+    mut mouse: ResMut<Input<MouseButton>>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    mut page_next: EventWriter<PageNextEvent>,
+    mut page_prev: EventWriter<PagePrevEvent>,
+    input: NavigationInput,
+    targets: Query<(Entity, &GlobalTransform, &Focusable, Option<&Sprite>, Option<&TextureAtlasSprite>)>,
+) {
+
+    for button in std::mem::take(&mut focus.release_next) {
+        mouse.release(button);
+    }
+
+    // touching the mouse hands control straight back to it
+    if cursor_moved.iter().count() > 0 && focus.active {
+        focus.active = false;
+        if mouse.pressed(MouseButton::Left) {
+            mouse.release(MouseButton::Left);
+        }
+    }
+
+    if input.pressed(KeyCode::PageUp, GamepadButtonType::LeftTrigger) {
+        page_prev.send(PagePrevEvent);
+    }
+    if input.pressed(KeyCode::PageDown, GamepadButtonType::RightTrigger) {
+        page_next.send(PageNextEvent);
+    }
+
+    let targets: Vec<(Entity, Vec2)> = targets.iter()
+        .filter_map(|(entity, transform, focusable, sprite, atlas_sprite)| {
+            focus_rect(transform, focusable, sprite, atlas_sprite).map(|(pos, _)| (entity, pos))
+        })
+        .collect();
+    let current_pos = focus.current.and_then(|current| targets.iter().find(|(entity, _)| *entity == current).map(|(_, pos)| *pos));
+
+    if let Some(direction) = read_direction(&input, &mut focus) {
+        let from = current_pos.unwrap_or_else(|| game_helper.mouse_world_pos());
+        let current = current_pos.and(focus.current);
+        if let Some(next) = next_in_direction(from, direction, &targets, current) {
+            focus.current = Some(next);
+        } else if current.is_none() {
+            // nothing that way, start from whatever is closest to the pointer
+            focus.current = targets.iter()
+                .min_by(|(_, a), (_, b)| a.distance(from).total_cmp(&b.distance(from)))
+                .map(|(entity, _)| *entity);
+        }
+        focus.active = true;
+    }
+
+    if !focus.active {
+        return;
+    }
+
+    let pos = focus.current.and_then(|current| targets.iter().find(|(entity, _)| *entity == current).map(|(_, pos)| *pos));
+    if let Some(pos) = pos {
+        game_helper.set_mouse_world_pos(pos);
+    }
+
+    // confirm presses the left button on the first go and lets go on the second, so it picks up
+    // and drops. if the press didn't pick anything up it is let go of right away, like a click
+    if input.pressed(KeyCode::Return, GamepadButtonType::South) {
+        if mouse.pressed(MouseButton::Left) {
+            mouse.release(MouseButton::Left);
+        } else {
+            mouse.press(MouseButton::Left);
+        }
+    }
+
+    // the second button stands in for right click, which opens the cookbook page
+    if input.pressed(KeyCode::I, GamepadButtonType::West) {
+        mouse.press(MouseButton::Right);
+        focus.release_next.push(MouseButton::Right);
+    }
+}
+
+pub fn release_empty_clicks(
+    mut focus: ResMut<Focus>,
+    mouse: Res<Input<MouseButton>>,
+    ui_data: Res<UiData>,
+) {
+    if focus.active && mouse.just_pressed(MouseButton::Left) && ui_data.currently_dragging.is_none() {
+        focus.release_next.push(MouseButton::Left);
+    }
+}
+
+//==================================================================================================
+//                          Focus Cursor
+//==================================================================================================
+
+#[derive(Component)]
+pub struct FocusCursor;
+
+fn spawn_focus_cursor(mut commands: Commands) {
+    commands.spawn_bundle(SpriteBundle {
+        sprite: Sprite {
+            color: CURSOR_COLOR,
+            ..default()
+        },
+        visibility: Visibility { is_visible: false },
+        ..default()
+    })
+        .insert(FocusCursor)
+        .insert(Name::new("Focus Cursor"));
+}
+
+// anything the cursor can be drawn around, and whichever sprite gives its size
+type CursorTargets<'w, 's> = Query<'w, 's, (&'static GlobalTransform, &'static Focusable, Option<&'static Sprite>, Option<&'static TextureAtlasSprite>), Without<FocusCursor>>;
+
+fn render_focus_cursor(
+    focus: Res<Focus>,
+    targets: CursorTargets,
+    mut cursor: Query<(&mut Transform, &mut Sprite, &mut Visibility), With<FocusCursor>>,
+) {
+    let (mut transform, mut sprite, mut visibility) = match cursor.get_single_mut() {
+        Ok(cursor) => cursor,
+        Err(_) => return,
+    };
+
+    let rect = focus.current
+        .filter(|_| focus.active)
+        .and_then(|current| targets.get(current).ok())
+        .and_then(|(target_transform, focusable, target_sprite, atlas_sprite)| focus_rect(target_transform, focusable, target_sprite, atlas_sprite));
+
+    visibility.is_visible = rect.is_some();
+    if let Some((pos, size)) = rect {
+        transform.translation = pos.extend(CURSOR_LEVEL);
+        sprite.custom_size = Some(size + Vec2::splat(CURSOR_PADDING));
+    }
+}
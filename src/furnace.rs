@@ -1,23 +1,28 @@
 use bevy::prelude::Res;
-use crate::element::Element;
-use crate::registry::{FurnaceRecipeIden, Registry};
+use crate::element::{Element, Tag};
+use crate::registry::Ingredient;
 
 #[derive(Eq, PartialEq, Debug, Clone, Default)]
 pub struct FurnaceRecipe {
-    pub fuel : Element,
-    pub object : Element,
+    pub fuel : Ingredient,
+    pub object : Ingredient,
     pub result : Element,
 }
 
+// only raw ingredients burn, so cooked food and finished dishes never end up as fuel
+const RAW_COLD: Ingredient = Ingredient::Tagged(&[Tag::Raw, Tag::Cold]);
+const RAW_HOT: Ingredient = Ingredient::Tagged(&[Tag::Raw, Tag::Hot]);
+
 impl FurnaceRecipe {
     // format: fuel, object, result
-    pub const GLACIER_ICE: FurnaceRecipe = FurnaceRecipe::new(Element::FROZEN_DRAGON_SCALE, Element::YETI_WATER, Element::GLACIER_ICE);
-    pub const ELVEN_BREAD: FurnaceRecipe = FurnaceRecipe::new(Element::MAGMA_PEPPER, Element::BREAD_DOUGH, Element::ELVEN_BREAD);
-    pub const ELVEN_TOAST: FurnaceRecipe = FurnaceRecipe::new(Element::MAGMA_PEPPER, Element::ELVEN_BREAD, Element::ELVEN_TOAST);
-    pub const SCRAMBLED_EGG: FurnaceRecipe = FurnaceRecipe::new(Element::MAGMA_PEPPER, Element::GRIFFON_EGG, Element::SCRAMBLED_EGG);
-    pub const BACON: FurnaceRecipe = FurnaceRecipe::new(Element::MAGMA_PEPPER, Element::RAW_BACON, Element::BACON);
-    pub const DRIED_SEAWEED: FurnaceRecipe = FurnaceRecipe::new(Element::MAGMA_PEPPER, Element::SIREN_SEAWEED, Element::DRIED_SEAWEED);
-    pub const BOILING_WATER: FurnaceRecipe = FurnaceRecipe::new(Element::MAGMA_PEPPER, Element::YETI_WATER, Element::BOILING_WATER);
+    // any raw cold fuel freezes, any raw hot fuel cooks
+    pub const GLACIER_ICE: FurnaceRecipe = FurnaceRecipe::new(RAW_COLD, Ingredient::Exact(Element::YETI_WATER), Element::GLACIER_ICE);
+    pub const ELVEN_BREAD: FurnaceRecipe = FurnaceRecipe::new(RAW_HOT, Ingredient::Exact(Element::BREAD_DOUGH), Element::ELVEN_BREAD);
+    pub const ELVEN_TOAST: FurnaceRecipe = FurnaceRecipe::new(RAW_HOT, Ingredient::Exact(Element::ELVEN_BREAD), Element::ELVEN_TOAST);
+    pub const SCRAMBLED_EGG: FurnaceRecipe = FurnaceRecipe::new(RAW_HOT, Ingredient::Exact(Element::GRIFFON_EGG), Element::SCRAMBLED_EGG);
+    pub const BACON: FurnaceRecipe = FurnaceRecipe::new(RAW_HOT, Ingredient::Exact(Element::RAW_BACON), Element::BACON);
+    pub const DRIED_SEAWEED: FurnaceRecipe = FurnaceRecipe::new(RAW_HOT, Ingredient::Exact(Element::SIREN_SEAWEED), Element::DRIED_SEAWEED);
+    pub const BOILING_WATER: FurnaceRecipe = FurnaceRecipe::new(RAW_HOT, Ingredient::Exact(Element::YETI_WATER), Element::BOILING_WATER);
 
     //pub const TEST: FurnaceRecipe = FurnaceRecipe::new(Element::FIRE_PEPPER, Element::YETI_WATER, Element::LEGEND_DAIRY);
 
@@ -31,7 +36,7 @@ impl FurnaceRecipe {
         FurnaceRecipe::BOILING_WATER
     ];
 
-    pub const fn new(fuel: Element, object: Element, result: Element) -> Self {
+    pub const fn new(fuel: Ingredient, object: Ingredient, result: Element) -> Self {
         Self {
             fuel,
             object,
@@ -40,7 +45,7 @@ impl FurnaceRecipe {
     }
 
     pub fn id(&self) -> String {
-        let id = format!("{}_{}", self.fuel.id, self.object.id);
+        let id = format!("{}_{}", self.fuel.id(), self.object.id());
         return id;
     }
 }
\ No newline at end of file
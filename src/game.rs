@@ -5,6 +5,7 @@ use crate::game::GameStatus::QuestComplete;
 use crate::gameflow::Gameflow;
 use crate::npc::{Npc, NPCData, NpcKind, Say};
 use crate::npc::NpcKind::Squee;
+use crate::plating::PlatingScore;
 use crate::quest::{CraftingTable, Quest};
 use crate::ui::{ElementCraftedEvent, InsertElementEvent, LoadMixerEvent, LoadSlicerEvent, RefreshSlotsEvent, UI_LEVEL, UiData};
 
@@ -44,6 +45,8 @@ pub struct GameManager {
     pub furnace_ent: Option<Entity>,
     pub npc: NpcKind,
     pub status: GameStatus,
+    // each course served to the boss so far, in order
+    pub boss_scores: Vec<(Element, PlatingScore)>,
 
     pub can_use_ui : bool
 }
@@ -58,6 +61,7 @@ impl Default for GameManager {
             furnace_ent: None,
             npc: NpcKind::Squee,
             status: GameStatus::QuestComplete,
+            boss_scores: Vec::new(),
             can_use_ui : false
         }
     }
@@ -1,14 +1,23 @@
 use std::collections::VecDeque;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy::text::Text2dBounds;
 use bevy::utils::HashMap;
 use bevy::utils::tracing::event;
-use crate::boss_fight::{SetupBossFightEvent, ToggleBossTimerEvent, WinGameEvent};
-use crate::audio::{SayEvent, MusicTrack, MusicChangeEvent};
+use rand::Rng;
+use crate::difficulty::DifficultyMenu;
+use crate::boss_fight::{BossTimerThresholdEvent, on_win_game, PlateServedEvent, SetupBossFightEvent, StartBossPhaseEvent, WinGameEvent, WinScreen};
+use crate::audio::SayEvent;
+use crate::audio::music::{MusicChangeEvent, MusicTrack};
 use crate::element::Element;
 use crate::game::GameManager;
-use crate::npc::{Npc, NpcClickEvent, NPCDropEvent, NpcKind, NpcSprite, NpcText, Say};
-use crate::ui::{CraftType, ElementCraftedEvent, InsertElementEvent, LoadFurnaceEvent, LoadMixerEvent, LoadSlicerEvent, NPC_LEVEL};
+use crate::npc::{MAIN_SPOT, Npc, NpcClickEvent, NPCDropEvent, NpcKind, NpcSprite, NpcText, Say};
+use crate::npc::animation::NpcAnimationState;
+use crate::npc::patience::{Mood, PatienceRanOutEvent};
+use crate::npc::reaction::{Reaction, Reactions};
+use crate::plating::{Grade, PlatingRecipe};
+use crate::registry::Registry;
+use crate::ui::{CraftType, ElementCraftedEvent, ElementRewardEvent, InsertElementEvent, LoadFurnaceEvent, LoadMixerEvent, LoadSlicerEvent, NPC_LEVEL};
 
 pub struct GameflowPlugin;
 
@@ -18,7 +27,8 @@ impl Plugin for GameflowPlugin {
             .init_resource::<Gameflow>()
             .init_resource::<GameManager>()
             //.add_startup_system(start_gameflow)
-            .add_system_to_stage(CoreStage::PostUpdate, update_gameflow);
+            // the win screen goes up the same frame, so the story is held from the next one on
+            .add_system_to_stage(CoreStage::PostUpdate, update_gameflow.before(on_win_game));
     }
 }
 
@@ -38,19 +48,31 @@ impl Gameflow {
         return self;
     }
 
+    /// Sets up the boss plate, then runs each phase as its intro, the plate and the verdict.
+    pub fn add_boss_fight(&mut self, fight : BossFight) -> &mut Self {
+        self.add_segment(StartBossFightSegment);
+        for phase in fight.phases {
+            self.add_segment(phase.intro)
+                .add_segment(phase.plate)
+                .add_segment(phase.verdict);
+        }
+        self
+    }
+
     pub fn advance(&mut self) {
         self.current += 1;
     }
 }
 
 pub struct EventCaller {
-    pub insert_element_event : Option<InsertElementEvent>,
+    pub insert_element_events : Vec<InsertElementEvent>,
+    pub reward_event : Option<ElementRewardEvent>,
     pub load_mixer_event : Option<LoadMixerEvent>,
     pub load_slicer_event : Option<LoadSlicerEvent>,
     pub load_furnace_event : Option<LoadFurnaceEvent>,
     pub say_event: Option<SayEvent>,
     pub setup_boss_event : Option<SetupBossFightEvent>,
-    pub toggle_timer_event : Option<ToggleBossTimerEvent>,
+    pub start_boss_phase_event : Option<StartBossPhaseEvent>,
     pub win_game_event : Option<WinGameEvent>,
     pub music_change_event: Option<MusicChangeEvent>,
 }
@@ -58,19 +80,76 @@ pub struct EventCaller {
 impl Default for EventCaller {
     fn default() -> Self {
         EventCaller {
-            insert_element_event : None,
+            insert_element_events : Vec::new(),
+            reward_event : None,
             say_event: None,
             load_mixer_event : None,
             load_slicer_event : None,
             load_furnace_event: None,
             setup_boss_event : None,
-            toggle_timer_event : None,
+            start_boss_phase_event : None,
             win_game_event : None,
             music_change_event: None,
         }
     }
 }
 
+impl EventCaller {
+    fn send_all(self, writers : &mut GameflowEventWriters) {
+        writers.insert_element.send_batch(self.insert_element_events.into_iter());
+
+        if let Some(event) = self.reward_event {
+            writers.reward.send(event)
+        }
+
+        if let Some(event) = self.load_furnace_event {
+            writers.load_furnace.send(event)
+        }
+
+        if let Some(event) = self.load_mixer_event {
+            writers.load_mixer.send(event)
+        }
+
+        if let Some(event) = self.load_slicer_event {
+            writers.load_slicer.send(event)
+        }
+
+        if let Some(event) = self.say_event {
+            writers.say.send(event)
+        }
+
+        if let Some(event) = self.setup_boss_event {
+            writers.setup_boss_fight.send(event)
+        }
+
+        if let Some(event) = self.start_boss_phase_event {
+            writers.start_boss_phase.send(event)
+        }
+
+        if let Some(event) = self.win_game_event {
+            writers.win_game.send(event)
+        }
+
+        if let Some(event) = self.music_change_event {
+            writers.music_change.send(event)
+        }
+    }
+}
+
+#[derive(SystemParam)]
+pub struct GameflowEventWriters<'w, 's> {
+    insert_element : EventWriter<'w, 's, InsertElementEvent>,
+    reward : EventWriter<'w, 's, ElementRewardEvent>,
+    load_furnace : EventWriter<'w, 's, LoadFurnaceEvent>,
+    load_mixer : EventWriter<'w, 's, LoadMixerEvent>,
+    load_slicer : EventWriter<'w, 's, LoadSlicerEvent>,
+    say : EventWriter<'w, 's, SayEvent>,
+    setup_boss_fight : EventWriter<'w, 's, SetupBossFightEvent>,
+    start_boss_phase : EventWriter<'w, 's, StartBossPhaseEvent>,
+    win_game : EventWriter<'w, 's, WinGameEvent>,
+    music_change : EventWriter<'w, 's, MusicChangeEvent>,
+}
+
 fn start_gameflow(
     mut gameflow: ResMut<Gameflow>,
     mut commands: Commands,
@@ -87,27 +166,38 @@ fn update_gameflow(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut game: ResMut<GameManager>,
+    registry: Res<Registry>,
 
     //Events Listeners
     mut on_npc_click: EventReader<NpcClickEvent>,
     mut on_item_craft: EventReader<ElementCraftedEvent>,
     mut on_npc_drop : EventReader<NPCDropEvent>,
+    mut on_patience_ran_out : EventReader<PatienceRanOutEvent>,
+    mut on_plate_served : EventReader<PlateServedEvent>,
+    mut on_boss_timer_threshold : EventReader<BossTimerThresholdEvent>,
+
+    difficulty_menu : Query<(), With<DifficultyMenu>>,
+    win_screen : Query<(), With<WinScreen>>,
 
     //Event Writers
-    mut insert_element_event_writer: EventWriter<InsertElementEvent>,
-    mut load_furnace_event_writer : EventWriter<LoadFurnaceEvent>,
-    mut load_mixer_event_writer : EventWriter<LoadMixerEvent>,
-    mut load_slicer_event_writer : EventWriter<LoadSlicerEvent>,
-    mut say_event_writer : EventWriter<SayEvent>,
-    mut setup_boss_fight : EventWriter<SetupBossFightEvent>,
-    mut toggle_boss_timer_event : EventWriter<ToggleBossTimerEvent>,
-    mut win_game_event : EventWriter<WinGameEvent>,
-    mut music_event_writer: EventWriter<MusicChangeEvent>,
+    mut writers : GameflowEventWriters,
 ) {
 
     //println!("{} | {}", gameflow.current, gameflow.segments.len());
     let mut event_caller = EventCaller::default();
 
+    // hold the story while one npc is walking out and the next is walking in, until a difficulty
+    // has been picked and while the win screen is up
+    if game.npc_data.is_transitioning() || !difficulty_menu.is_empty() || !win_screen.is_empty() {
+        on_npc_click.clear();
+        on_item_craft.clear();
+        on_npc_drop.clear();
+        on_patience_ran_out.clear();
+        on_plate_served.clear();
+        on_boss_timer_threshold.clear();
+        return;
+    }
+
     let mut should_init = false;
 
     if gameflow.current != gameflow.last {
@@ -130,46 +220,28 @@ fn update_gameflow(
         }
 
         for event in on_npc_drop.iter() {
-            current.on_npc_drop(&mut commands, &asset_server, &mut game, &mut event_caller, event.0.clone())
+            current.on_npc_drop(&mut commands, &asset_server, &mut game, &mut event_caller, &registry, event.0.clone(), event.1)
         }
 
-        if current.is_complete() {
-            current.on_segment_end(&mut commands, &asset_server, &mut game, &mut event_caller);
-            gameflow.advance();
+        for event in on_patience_ran_out.iter() {
+            current.on_patience_ran_out(&mut commands, &asset_server, &mut game, &mut event_caller, event.0)
         }
-    }
-
-    if let Some(event) = event_caller.insert_element_event {
-        insert_element_event_writer.send(event)
-    }
-
-    if let Some(event) = event_caller.load_furnace_event {
-        load_furnace_event_writer.send(event)
-    }
-
-    if let Some(event) = event_caller.load_mixer_event {
-        load_mixer_event_writer.send(event)
-    }
-
-    if let Some(event) = event_caller.load_slicer_event {
-        load_slicer_event_writer.send(event)
-    }
 
-    if let Some(event) = event_caller.say_event {
-        say_event_writer.send(event)
-    }
+        for event in on_plate_served.iter() {
+            current.on_plate_served(&mut commands, &asset_server, &mut game, &mut event_caller, event.0.clone())
+        }
 
-    if let Some(event) = event_caller.setup_boss_event {
-        setup_boss_fight.send(event)
-    }
+        for event in on_boss_timer_threshold.iter() {
+            current.on_boss_timer_threshold(&mut commands, &asset_server, &mut game, &mut event_caller, event.0)
+        }
 
-    if let Some(event) = event_caller.toggle_timer_event {
-        toggle_boss_timer_event.send(event)
+        if current.is_complete() {
+            current.on_segment_end(&mut commands, &asset_server, &mut game, &mut event_caller);
+            gameflow.advance();
+        }
     }
 
-    if let Some(event) = event_caller.music_change_event {
-        music_event_writer.send(event)
-    }
+    event_caller.send_all(&mut writers);
 }
 
 impl Default for Gameflow {
@@ -292,9 +364,10 @@ impl Default for Gameflow {
                 .with_hint("So please make me a sandwich of some sort!")
                 .with_hint("My favorite part of any sandwich is the bread. Good bread is necessary for a good sandwich.")
                 .with_hint("Ill take any type of sandwich, really!")
-                .with_comment(&Element::ELVEN_BREAD, "Yes! Any good sandwich needs some bread!")
+                .with_close_line("That smells like the makings of a sandwich! Keep at it!")
+                .with_emotional_comment(&Element::ELVEN_BREAD, "Yes! Any good sandwich needs some bread!", NpcAnimationState::Happy)
                 .with_comment(&Element::BREAD_DOUGH, "A step in the right direction! You could be a knight yourself with intuition like that!")
-                .with_comment(&Element::ICE_CREAM_SANDWICH, "That is what I require! Hand it over at once!")
+                .with_emotional_comment(&Element::ICE_CREAM_SANDWICH, "That is what I require! Hand it over at once!", NpcAnimationState::Happy)
             )
 
             .add_segment(NpcDialogueSegment::new()
@@ -331,25 +404,31 @@ impl Default for Gameflow {
             )
 
             .add_segment(CraftingSegment::new(Element::SALAD.clone(), false)
+                .with_patience(240.)
                 .with_hint("... Could you please make me one now? ...")
                 .with_hint("... a salad with a creamy and crunchy topping ...")
                 .with_hint("... I want those crunchy things ... crawdads ... cruonans ... ahh, something like that ...")
                 .with_hint("... could you please hurry? I need to get back to the RANCH ...")
                 .with_hint("... I like the toppings mixed together ...")
+                .with_loved(&Element::SIREN_SEAWEED, "... oh ... that is the green part of my salad ... you still need to make the rest ...")
                 .with_comment(&Element::MAYO, "... that seems creamy, but to solid for a salad ...")
-                .with_comment(&Element::ELVEN_TOAST, "... that smells good, but that wont fit on my salad ...")
+                .with_emotional_comment(&Element::ELVEN_TOAST, "... that smells good, but that wont fit on my salad ...", NpcAnimationState::Disgusted)
                 .with_comment(&Element::DICED_CROUTONS, "... those would add the most perfect crunch to my salad ...")
                 .with_comment(&Element::RANCH, "... that seems yummy ... perfect for my salad ...")
-                .with_comment(&Element::SALAD, "... oh wow that looks so good ... can I please have it?")
+                .with_emotional_comment(&Element::SALAD, "... oh wow that looks so good ... can I please have it?", NpcAnimationState::Happy)
             )
 
             .add_segment(NpcDialogueSegment::new()
                 .with_line("... thanks ... this salad looks really good ... ")
                 .with_line("... I am going to go home now ... I have been in public for far too long ...")
+                .with_ran_out_line("... I cant wait any longer ... I will just eat the seaweed on its own ...")
+                .with_ran_out_line("... I am going to go home now ... I have been in public for far too long ...")
             )
 
             .add_segment(GiveElementSegment::new(Element::RAW_PORK)
                 .with_line("... here is something from my pig farm as payment ...")
+                .with_bonus(Element::RAW_BACON, Mood::Content)
+                .with_bonus_line("... here is something from my pig farm as payment ... and some bacon, since you were so quick ...")
             )
 
             .add_segment(TransitionSegment::new(
@@ -375,10 +454,11 @@ impl Default for Gameflow {
             .add_segment(CraftingSegment::new(Element::CUT_SANDWICH.clone(), false)
                 .with_hint("I am in need of a breakfast sandwich with a little bit of heat.")
                 .with_hint("Mhhmm. That cut of pork looks mighty delicious!")
-                .with_comment(&Element::PEPPER_FLAKES, "Yes not the whole pepper, just a bit of it. However, I still think those flakes are going to be hard to sallow...")
+                .with_hated(&Element::RAW_PORK, "I asked for a sandwich, not a whole pig!")
+                .with_emotional_comment(&Element::PEPPER_FLAKES, "Yes not the whole pepper, just a bit of it. However, I still think those flakes are going to be hard to sallow...", NpcAnimationState::Disgusted)
                 .with_comment(&Element::SCRAMBLED_EGG, "What a good filling for a breakfast sandwich! I think it is missing a protein though.")
                 .with_comment(&Element::RAW_BACON, "Now that looks intriguing! Sliced pork? How novel.")
-                .with_comment(&Element::BACON, "Listen to that sizzle, music to my ears and ambrosia for my nose!")
+                .with_emotional_comment(&Element::BACON, "Listen to that sizzle, music to my ears and ambrosia for my nose!", NpcAnimationState::Happy)
                 .with_comment(&Element::SANDWICH_FILLINGS, "The perfect mix of filling fillings I have ever seen.")
                 .with_comment(&Element::SPICY_SPREAD, "That will be the perfect amount of heat! Put it on the sandwich!")
                 .with_comment(&Element::SPICY_TOAST, "Now all that needs is the filling!")
@@ -400,7 +480,7 @@ impl Default for Gameflow {
                 ]
             ))
 
-            .add_segment(MusicChangeSegment::change_too(MusicTrack::Boss))
+            .add_segment(MusicChangeSegment::change_too(MusicTrack::BOSS))
 
 
             .add_segment(NpcDialogueSegment::new()
@@ -411,50 +491,73 @@ impl Default for Gameflow {
                 .with_line("Where is your menu? Or is the only you serve here bad service and the black plague.")
                 .with_line("Disgusting. I hope that your food comes cooked and not BLOODY RAW!")
                 .with_line("Hmph. Now, I think I have berated you enough to work up an appetite. I will order my food now.")
-                .with_line("I want you to make your best ramen. A true mark of any great chef is to incorporate foreign dished into your repertoire.")
-                .with_line("I will see your skill by eating a dish that is uncommon. Also there is a twist.")
+                .with_line("Two courses. An appetizer, then the main. Also there is a twist.")
             )
 
-            .add_segment(StartBossFightSegment)
-
-            .add_segment(NpcDialogueSegment::new()
-                .with_line("I will time you. You have 10 minutes to complete the dish.")
-                .with_line("This ramen must be made with eight ingredients only. I will give you a description of what I want now.")
-                .with_line("Listen close, because I wont repeat myself.")
-                .with_line("Seriously, I WONT repeat myself.")
-                .with_line("I want a spicy ramen with a delicious broth and traditionally cooked meat.")
-                .with_line("The noodles better be made from scratch, I will be able to tell. Garnish with a green and a hearty ingredient.")
-                .with_line("Once bowled, I want it to be served with utensils and an ice cube to cool it down.")
-                .with_line("Do you understand? Good. Your time starts...")
+            .add_boss_fight(BossFight::new()
+                .with_phase(BossPhase::new(PlatingRecipe::SALAD, 180)
+                    .with_intro_line("I will time you. You have until that clock runs out to plate each course.")
+                    .with_intro_line("First, a salad. Greens from the sea and a proper topping.")
+                    .with_intro_line("Croutons or an egg on top, if you want to impress me.")
+                    .with_intro_line("Your time starts... NOW!")
+                    .with_hint("The salad! The clock is ticking.")
+                    .with_hint("Greens and a topping. It is not difficult.")
+                    .with_wrong_line("Do not hand me things. Put the salad on the plate.")
+                    .with_comment(&Element::SALAD_TOPPING, "At least you know what a topping is.")
+                    .with_taunt(60, "One minute. My grandmother tosses salads faster, and she is a ghost.")
+                    .with_taunt(10, "TEN SECONDS!")
+                    .with_verdict(Grade::S, &["Crisp, fresh and dressed just right. Hmph. Not bad at all."])
+                    .with_verdict(Grade::A, &["A decent salad. Do not let it go to your head."])
+                    .with_verdict(Grade::B, &["It is a salad. Barely."])
+                    .with_verdict(Grade::C, &["I have seen better salads in a compost heap. Let us hope the main course is an improvement."])
+                )
+                .with_phase(BossPhase::new(PlatingRecipe::RAMEN, 600)
+                    .with_intro_line("Now for the main course. I want you to make your best ramen.")
+                    .with_intro_line("A true mark of any great chef is to incorporate foreign dished into your repertoire.")
+                    .with_intro_line("This ramen must be made with eight ingredients only. I will give you a description of what I want now.")
+                    .with_intro_line("Listen close, because I wont repeat myself.")
+                    .with_intro_line("Seriously, I WONT repeat myself.")
+                    .with_intro_line("I want a spicy ramen with a delicious broth and traditionally cooked meat.")
+                    .with_intro_line("The noodles better be made from scratch, I will be able to tell. Garnish with a green and a hearty ingredient.")
+                    .with_intro_line("Once bowled, I want it to be served with utensils and an ice cube to cool it down.")
+                    .with_intro_line("Do you understand? Good. Your time starts... NOW!!!!")
+                    .with_hint("Make the ramen! What what are you waiting for?")
+                    .with_hint("I told you I wouldn't repeat myself.")
+                    .with_wrong_line("Do not hand me things. Put the ramen in the bowl.")
+                    .with_comment(&Element::PORK_BROTH, "Wow. Good use of your ingredients.")
+                    .with_comment(&Element::BONE_CHOPSTICK, "I see what you are doing there. Very smart.")
+                    .with_emotional_comment(&Element::BONE_CHOPSTICKS, "Perfect. I can eat with those.", NpcAnimationState::Happy)
+                    .with_comment(&Element::DRIED_SEAWEED, "Yes, that will do nicely.")
+                    .with_comment(&Element::CHASHU, "Perfectly cooked and cut. A man class after all.")
+                    .with_comment(&Element::NOODLE_DOUGH, "Ah, interesting.")
+                    .with_comment(&Element::RAMEN_NOODLES, "That is a nice cut of noodles.")
+                    .with_emotional_comment(&Element::BOILING_WATER, "Interesting...", NpcAnimationState::Disgusted)
+                    .with_comment(&Element::HARD_BOILED_EGG, "Perfect addition to my ramen.")
+                    .with_taunt(300, "Five minutes left. I can hear my stomach judging you.")
+                    .with_taunt(60, "One minute! Is the broth still in the pot?")
+                    .with_taunt(10, "TEN SECONDS! PUT IT IN THE BOWL!")
+                    .with_verdict(Grade::S, &[
+                        "Wow... this... is... actually good.",
+                        "I dont say that often, but this ramen is actually very good.",
+                        "Full credits. This is a good dish. Well done!",
+                    ])
+                    .with_verdict(Grade::A, &[
+                        "Hm. The broth is rich and the noodles have bite.",
+                        "It is not perfect, but I would order this again. Well done.",
+                    ])
+                    .with_verdict(Grade::B, &[
+                        "It is ramen. I will give you that much.",
+                        "Edible, but I have had better from a vending machine. Try harder next time.",
+                    ])
+                    .with_verdict(Grade::C, &[
+                        "What... is... this?",
+                        "You put everything in the bowl, and that is the nicest thing I can say about it.",
+                        "I will eat it, but only because I am starving. Do not expect a good review.",
+                    ])
+                )
             )
 
-            .add_segment(ToggleBossTimerSegment)
-
-            .add_segment(NpcDialogueSegment::new()
-                .with_line("NOW!!!!")
-            )
-
-            .add_segment(CraftingSegment::new(Element::RAMEN, true)
-                .with_hint("Make the ramen! What what are you waiting for?")
-                .with_hint("I told you I wouldn't repeat myself.")
-                .with_comment(&Element::PORK_BROTH, "Wow. Good use of your ingredients.")
-                .with_comment(&Element::BONE_CHOPSTICK, "I see what you are doing there. Very smart.")
-                .with_comment(&Element::BONE_CHOPSTICKS, "Perfect. I can eat with those.")
-                .with_comment(&Element::DRIED_SEAWEED, "Yes, that will do nicely.")
-                .with_comment(&Element::CHASHU, "Perfectly cooked and cut. A man class after all.")
-                .with_comment(&Element::NOODLE_DOUGH, "Ah, interesting.")
-                .with_comment(&Element::RAMEN_NOODLES, "That is a nice cut of noodles.")
-                .with_comment(&Element::BOILING_WATER, "Interesting...")
-                .with_comment(&Element::HARD_BOILED_EGG, "Perfect addition to my ramen.")
-            )
-
-            .add_segment(NpcDialogueSegment::new()
-                .with_line("Wow... this... is... actually good.")
-                .with_line("I dont say that often, but this ramen is actually very good.")
-                .with_line("Full credits. This is a good dish. Well done!")
-            )
-
-            //.add_segment(MusicChangeSegment::change_too(MusicTrack::Background))
+            //.add_segment(MusicChangeSegment::change_too(MusicTrack::BACKGROUND))
             .add_segment(WinGameSegment)
         ;
 
@@ -476,6 +579,24 @@ pub trait Segment {
         element : Element
     ) {}
 
+    fn on_plate_served(
+        &mut self,
+        commands: &mut Commands,
+        asset_server: &Res<AssetServer>,
+        game: &mut ResMut<GameManager>,
+        event_caller : &mut EventCaller,
+        dish : Element
+    ) {}
+
+    fn on_boss_timer_threshold(
+        &mut self,
+        commands: &mut Commands,
+        asset_server: &Res<AssetServer>,
+        game: &mut ResMut<GameManager>,
+        event_caller : &mut EventCaller,
+        seconds_left : u64
+    ) {}
+
     fn on_npc_click(
         &mut self,
         commands: &mut Commands,
@@ -490,7 +611,18 @@ pub trait Segment {
         asset_server: &Res<AssetServer>,
         game: &mut ResMut<GameManager>,
         event_caller : &mut EventCaller,
-        element : Element
+        registry : &Res<Registry>,
+        element : Element,
+        spot : usize
+    ) {}
+
+    fn on_patience_ran_out(
+        &mut self,
+        commands: &mut Commands,
+        asset_server: &Res<AssetServer>,
+        game: &mut ResMut<GameManager>,
+        event_caller : &mut EventCaller,
+        spot : usize
     ) {}
 
     fn on_segment_start(
@@ -516,6 +648,7 @@ pub trait Segment {
 
 struct NpcDialogueSegment {
     phrases: VecDeque<String>,
+    ran_out_phrases: VecDeque<String>,
     ready_to_advance : bool
 }
 
@@ -523,6 +656,7 @@ impl NpcDialogueSegment {
     pub fn new() -> Self {
         Self {
             phrases: VecDeque::new(),
+            ran_out_phrases: VecDeque::new(),
             ready_to_advance : false,
         }
     }
@@ -539,6 +673,12 @@ impl NpcDialogueSegment {
         self.phrases.push_back(line.to_string());
         self
     }
+
+    /// Said instead of the regular lines when the customer gave up on their last order.
+    pub fn with_ran_out_line(mut self, line : &str) -> Self {
+        self.ran_out_phrases.push_back(line.to_string());
+        self
+    }
 }
 
 impl Segment for NpcDialogueSegment {
@@ -561,10 +701,56 @@ impl Segment for NpcDialogueSegment {
     }
 
     fn on_segment_start(&mut self, commands: &mut Commands, asset_server: &Res<AssetServer>, game: &mut ResMut<GameManager>, event_caller: &mut EventCaller) {
+        if game.npc_data.ran_out_at(MAIN_SPOT) && !self.ran_out_phrases.is_empty() {
+            self.phrases = std::mem::take(&mut self.ran_out_phrases);
+        }
         self.do_next_phrase(commands, game, event_caller);
     }
 }
 
+//==================================================================================================
+//                    Graded Dialogue Segment
+//==================================================================================================
+
+/// Dialogue that depends on how well the last boss dish was graded.
+pub struct GradedDialogueSegment {
+    lines : HashMap<Grade, Vec<String>>,
+    dialogue : NpcDialogueSegment,
+}
+
+impl GradedDialogueSegment {
+    pub fn new() -> Self {
+        Self {
+            lines : HashMap::new(),
+            dialogue : NpcDialogueSegment::new(),
+        }
+    }
+
+    pub fn with_lines(mut self, grade : Grade, lines : &[&str]) -> Self {
+        self.lines.insert(grade, lines.iter().map(|line| line.to_string()).collect());
+        self
+    }
+}
+
+impl Segment for GradedDialogueSegment {
+    fn is_complete(&self) -> bool {
+        self.dialogue.is_complete()
+    }
+
+    fn on_npc_click(&mut self, commands: &mut Commands, asset_server: &Res<AssetServer>, game: &mut ResMut<GameManager>, event_caller: &mut EventCaller) {
+        self.dialogue.on_npc_click(commands, asset_server, game, event_caller)
+    }
+
+    fn on_segment_start(&mut self, commands: &mut Commands, asset_server: &Res<AssetServer>, game: &mut ResMut<GameManager>, event_caller: &mut EventCaller) {
+        // a plate that was never graded gets the lowest tier rather than nothing at all
+        let grade = game.boss_scores.last().map(|(_, score)| score.grade()).unwrap_or(Grade::C);
+        if let Some(lines) = self.lines.get(&grade) {
+            self.dialogue.phrases = lines.iter().cloned().collect();
+        }
+        self.dialogue.on_segment_start(commands, asset_server, game, event_caller)
+    }
+}
+
 //==================================================================================================
 //                    Crafting Segment
 //==================================================================================================
@@ -573,9 +759,13 @@ pub struct CraftingSegment {
     goal : Element,
     hints : Vec<String>,
     comments : HashMap<Element, String>,
+    emotions : HashMap<Element, NpcAnimationState>,
+    reactions : Reactions,
     is_thing_crafted : bool,
+    gave_up : bool,
     current_hint : usize,
-    continue_on_craft : bool
+    continue_on_craft : bool,
+    patience : Option<f32>,
 }
 
 impl CraftingSegment {
@@ -585,9 +775,13 @@ impl CraftingSegment {
             goal : element,
             hints : Vec::new(),
             comments : HashMap::new(),
+            emotions : HashMap::new(),
+            reactions : Reactions::default(),
             is_thing_crafted : false,
+            gave_up : false,
             current_hint : 0,
-            continue_on_craft
+            continue_on_craft,
+            patience : None,
         }
     }
 
@@ -601,6 +795,38 @@ impl CraftingSegment {
         self
     }
 
+    pub fn with_emotional_comment(mut self, element : &'static Element, comment : &str, emotion : NpcAnimationState) -> CraftingSegment {
+        self.emotions.insert(element.clone(), emotion);
+        self.with_comment(element, comment)
+    }
+
+    pub fn with_wrong_line(mut self, line : &str) -> CraftingSegment {
+        self.reactions = self.reactions.with_wrong_line(line);
+        self
+    }
+
+    pub fn with_close_line(mut self, line : &str) -> CraftingSegment {
+        self.reactions = self.reactions.with_close_line(line);
+        self
+    }
+
+    pub fn with_loved(mut self, element : &'static Element, line : &str) -> CraftingSegment {
+        self.reactions = self.reactions.with_loved(element, line);
+        self
+    }
+
+    pub fn with_hated(mut self, element : &'static Element, line : &str) -> CraftingSegment {
+        self.reactions = self.reactions.with_hated(element, line);
+        self
+    }
+
+    /// The customer only waits this many seconds before giving up on the order and the story moves
+    /// on without it. Their mood decides what they pay out afterwards.
+    pub fn with_patience(mut self, seconds : f32) -> CraftingSegment {
+        self.patience = Some(seconds);
+        self
+    }
+
     pub fn cycle_hint(&mut self, commands : &mut Commands, game : &mut ResMut<GameManager>, event_caller: &mut EventCaller) {
         if self.current_hint >= self.hints.len() {
             self.current_hint = 0
@@ -614,7 +840,7 @@ impl CraftingSegment {
 
 impl Segment for CraftingSegment {
     fn is_complete(&self) -> bool {
-        self.is_thing_crafted
+        self.is_thing_crafted || self.gave_up
     }
 
     fn on_item_crafted(&mut self, commands: &mut Commands, asset_server: &Res<AssetServer>, game: &mut ResMut<GameManager>, event_caller: &mut EventCaller, element: Element) {
@@ -628,6 +854,10 @@ impl Segment for CraftingSegment {
             let duration = game.npc_data.say(commands, comment.as_str());
             event_caller.say_event = Some(SayEvent(duration));
         }
+
+        if let Some(emotion) = self.emotions.get(&element) {
+            game.npc_data.emote(commands, *emotion);
+        }
     }
 
     fn on_npc_click(
@@ -644,22 +874,48 @@ impl Segment for CraftingSegment {
         asset_server: &Res<AssetServer>,
         game: &mut ResMut<GameManager>,
         event_caller: &mut EventCaller,
-        element: Element
+        registry: &Res<Registry>,
+        element: Element,
+        spot: usize
     ) {
-        if !self.continue_on_craft {
-            if element == self.goal {
+        if element == self.goal {
+            if !self.continue_on_craft {
                 self.is_thing_crafted = true;
             }
+            return;
+        }
+
+        // the segment's own lines win over the customer's, and both win over the generic ones
+        let steps = registry.steps_between(&element, &self.goal);
+        let reaction = self.reactions.react(&element, steps)
+            .or_else(|| game.npc_data.npc_at(spot).and_then(|npc| npc.reactions.react(&element, steps)))
+            .unwrap_or_else(|| Reaction::generic(steps));
+
+        game.npc_data.fail_handoff(spot);
+        let duration = game.npc_data.say_at(commands, spot, reaction.line.as_str());
+        event_caller.say_event = Some(SayEvent(duration));
+        if let Some(emotion) = reaction.emotion {
+            game.npc_data.emote_at(commands, spot, emotion);
         }
     }
 
+    // story customers stay at the counter, what they say about it is up to the next segment
+    fn on_patience_ran_out(&mut self, commands: &mut Commands, asset_server: &Res<AssetServer>, game: &mut ResMut<GameManager>, event_caller: &mut EventCaller, spot: usize) {
+        self.gave_up = true;
+        game.npc_data.emote_at(commands, spot, NpcAnimationState::Disgusted);
+    }
+
     fn on_segment_start(&mut self, commands: &mut Commands, asset_server: &Res<AssetServer>, game: &mut ResMut<GameManager>, event_caller: &mut EventCaller) {
         self.cycle_hint(commands, game, event_caller);
         game.can_use_ui = true;
+        if let Some(seconds) = self.patience {
+            game.npc_data.start_patience(MAIN_SPOT, seconds);
+        }
     }
 
     fn on_segment_end(&mut self, commands: &mut Commands, asset_server: &Res<AssetServer>, game: &mut ResMut<GameManager>, event_caller: &mut EventCaller) {
-        game.can_use_ui = false
+        game.can_use_ui = false;
+        game.npc_data.stop_patience(MAIN_SPOT);
     }
 }
 
@@ -670,6 +926,8 @@ impl Segment for CraftingSegment {
 pub struct GiveElementSegment {
     element : Element,
     optional_dialog : Option<String>,
+    bonuses : Vec<(Element, Mood)>,
+    bonus_dialog : Option<String>,
     can_continue : bool
 }
 
@@ -678,6 +936,8 @@ impl GiveElementSegment {
         Self {
             element,
             optional_dialog: None,
+            bonuses : Vec::new(),
+            bonus_dialog : None,
             can_continue : false
         }
     }
@@ -686,6 +946,18 @@ impl GiveElementSegment {
         self.optional_dialog = Some(line.to_string());
         self
     }
+
+    /// Also pays out `element` if the customer is still in at least `mood`.
+    pub fn with_bonus(mut self, element : Element, mood : Mood) -> Self {
+        self.bonuses.push((element, mood));
+        self
+    }
+
+    /// Said instead of the regular line when any bonus gets paid out.
+    pub fn with_bonus_line(mut self, line : &str) -> Self {
+        self.bonus_dialog = Some(line.to_string());
+        self
+    }
 }
 
 impl Segment for GiveElementSegment {
@@ -698,8 +970,24 @@ impl Segment for GiveElementSegment {
     }
 
     fn on_segment_start(&mut self, commands: &mut Commands, asset_server: &Res<AssetServer>, game: &mut ResMut<GameManager>, event_caller: &mut EventCaller) {
-        event_caller.insert_element_event = Some(InsertElementEvent(self.element.clone()));
-        if let Some(dialog) = &self.optional_dialog {
+        let mood = game.npc_data.mood_at(MAIN_SPOT);
+        let mut reward = vec![self.element.clone()];
+        for (element, min_mood) in self.bonuses.iter() {
+            if mood >= *min_mood {
+                reward.push(element.clone());
+            }
+        }
+        let paid_bonus = reward.len() > 1;
+
+        event_caller.insert_element_events.extend(reward.iter().cloned().map(InsertElementEvent));
+        event_caller.reward_event = Some(ElementRewardEvent(reward));
+
+        let dialog = if paid_bonus {
+            self.bonus_dialog.as_ref().or(self.optional_dialog.as_ref())
+        } else {
+            self.optional_dialog.as_ref()
+        };
+        if let Some(dialog) = dialog {
             let duration = game.npc_data.say(commands, dialog.as_str());
             event_caller.say_event = Some(SayEvent(duration));
         }
@@ -723,18 +1011,138 @@ impl Segment for StartBossFightSegment {
 }
 
 //==================================================================================================
-//                    Toggle Boss Timer Segment
+//                    Boss Phase Segment
 //==================================================================================================
 
-pub struct ToggleBossTimerSegment;
+/// One course of a boss fight. Resets the plate and clock when it starts and finishes once the
+/// right dish is served. In between it hints and comments like a crafting segment.
+pub struct BossPhaseSegment {
+    plating : PlatingRecipe,
+    seconds : u64,
+    crafting : CraftingSegment,
+    taunts : HashMap<u64, String>,
+    is_served : bool,
+}
 
-impl Segment for ToggleBossTimerSegment {
+impl Segment for BossPhaseSegment {
     fn is_complete(&self) -> bool {
-        true
+        self.is_served
+    }
+
+    fn on_item_crafted(&mut self, commands: &mut Commands, asset_server: &Res<AssetServer>, game: &mut ResMut<GameManager>, event_caller: &mut EventCaller, element: Element) {
+        self.crafting.on_item_crafted(commands, asset_server, game, event_caller, element)
+    }
+
+    fn on_plate_served(&mut self, commands: &mut Commands, asset_server: &Res<AssetServer>, game: &mut ResMut<GameManager>, event_caller: &mut EventCaller, dish: Element) {
+        if dish == self.plating.dish {
+            self.is_served = true;
+        }
+    }
+
+    fn on_boss_timer_threshold(&mut self, commands: &mut Commands, asset_server: &Res<AssetServer>, game: &mut ResMut<GameManager>, event_caller: &mut EventCaller, seconds_left: u64) {
+        if let Some(taunt) = self.taunts.get(&seconds_left) {
+            let duration = game.npc_data.say(commands, taunt.as_str());
+            event_caller.say_event = Some(SayEvent(duration));
+        }
+    }
+
+    fn on_npc_click(&mut self, commands: &mut Commands, asset_server: &Res<AssetServer>, game: &mut ResMut<GameManager>, event_caller: &mut EventCaller) {
+        self.crafting.on_npc_click(commands, asset_server, game, event_caller)
+    }
+
+    fn on_npc_drop(&mut self, commands: &mut Commands, asset_server: &Res<AssetServer>, game: &mut ResMut<GameManager>, event_caller: &mut EventCaller, registry: &Res<Registry>, element: Element, spot: usize) {
+        self.crafting.on_npc_drop(commands, asset_server, game, event_caller, registry, element, spot)
     }
 
     fn on_segment_start(&mut self, commands: &mut Commands, asset_server: &Res<AssetServer>, game: &mut ResMut<GameManager>, event_caller: &mut EventCaller) {
-        event_caller.toggle_timer_event = Some(ToggleBossTimerEvent)
+        event_caller.start_boss_phase_event = Some(StartBossPhaseEvent {
+            plating : self.plating.clone(),
+            seconds : self.seconds,
+        });
+        self.crafting.on_segment_start(commands, asset_server, game, event_caller)
+    }
+
+    fn on_segment_end(&mut self, commands: &mut Commands, asset_server: &Res<AssetServer>, game: &mut ResMut<GameManager>, event_caller: &mut EventCaller) {
+        self.crafting.on_segment_end(commands, asset_server, game, event_caller)
+    }
+}
+
+/// A course of a boss fight: what the boss says before the clock starts, the plate itself and
+/// how the boss reacts to the grade.
+pub struct BossPhase {
+    intro : NpcDialogueSegment,
+    plate : BossPhaseSegment,
+    verdict : GradedDialogueSegment,
+}
+
+impl BossPhase {
+    /// `seconds` is the time on the clock on normal difficulty.
+    pub fn new(plating : PlatingRecipe, seconds : u64) -> Self {
+        BossPhase {
+            intro : NpcDialogueSegment::new(),
+            plate : BossPhaseSegment {
+                crafting : CraftingSegment::new(plating.dish.clone(), true),
+                plating,
+                seconds,
+                taunts : HashMap::new(),
+                is_served : false,
+            },
+            verdict : GradedDialogueSegment::new(),
+        }
+    }
+
+    pub fn with_intro_line(mut self, line : &str) -> Self {
+        self.intro = self.intro.with_line(line);
+        self
+    }
+
+    pub fn with_hint(mut self, hint : &str) -> Self {
+        self.plate.crafting = self.plate.crafting.with_hint(hint);
+        self
+    }
+
+    pub fn with_wrong_line(mut self, line : &str) -> Self {
+        self.plate.crafting = self.plate.crafting.with_wrong_line(line);
+        self
+    }
+
+    pub fn with_comment(mut self, element : &'static Element, comment : &str) -> Self {
+        self.plate.crafting = self.plate.crafting.with_comment(element, comment);
+        self
+    }
+
+    pub fn with_emotional_comment(mut self, element : &'static Element, comment : &str, emotion : NpcAnimationState) -> Self {
+        self.plate.crafting = self.plate.crafting.with_emotional_comment(element, comment, emotion);
+        self
+    }
+
+    /// Said when the clock gets down to `seconds_left`, one of the boss clock thresholds.
+    pub fn with_taunt(mut self, seconds_left : u64, line : &str) -> Self {
+        self.plate.taunts.insert(seconds_left, line.to_string());
+        self
+    }
+
+    pub fn with_verdict(mut self, grade : Grade, lines : &[&str]) -> Self {
+        self.verdict = self.verdict.with_lines(grade, lines);
+        self
+    }
+}
+
+/// A customer that is served course after course on the boss plate.
+pub struct BossFight {
+    phases : Vec<BossPhase>,
+}
+
+impl BossFight {
+    pub fn new() -> Self {
+        BossFight {
+            phases : Vec::new(),
+        }
+    }
+
+    pub fn with_phase(mut self, phase : BossPhase) -> Self {
+        self.phases.push(phase);
+        self
     }
 }
 
@@ -742,6 +1150,7 @@ impl Segment for ToggleBossTimerSegment {
 //                    Win Game Segment
 //==================================================================================================
 
+/// Ends the story. The win screen grades the run from the scores of every boss course.
 pub struct WinGameSegment;
 
 impl Segment for WinGameSegment {
@@ -825,6 +1234,15 @@ impl TransitionSegment {
         }
     }
 
+    /// Walks the next npc in, fading to their theme while the old one leaves.
+    fn swap_npc(&mut self, commands: &mut Commands, game: &mut ResMut<GameManager>, event_caller: &mut EventCaller) {
+        if let Some(theme) = game.npc_data.next_story_npc().and_then(|npc| npc.theme) {
+            event_caller.music_change_event = Some(MusicChangeEvent::to(theme));
+        }
+        let phrase = self.get_next_phrase();
+        game.npc_data.swap_npc(commands, phrase.as_str());
+    }
+
     pub fn is_old_npc_done(&self) -> bool {
         self.leaving_index >= (self.leaving_phrases.len() as i32) - 1
     }
@@ -862,7 +1280,8 @@ impl Segment for TransitionSegment {
             return;
         }
         if self.is_old_npc_done() && self.entering_index == -1 { // -1 because the 0 index of the dialogue Vec has not been said
-            game.npc_data.spawn_next_npc()
+            self.swap_npc(commands, game, event_caller);
+            return;
         }
         let phrase = self.get_next_phrase();
         let duration = game.npc_data.say(commands, phrase.as_str());
@@ -874,7 +1293,8 @@ impl Segment for TransitionSegment {
             return;
         }
         if self.is_old_npc_done() && self.entering_index == -1 { // -1 because the 0 index of the dialogue Vec has not been said
-            game.npc_data.spawn_next_npc()
+            self.swap_npc(commands, game, event_caller);
+            return;
         }
         let phrase = self.get_next_phrase();
         let duration = game.npc_data.say(commands, phrase.as_str());
@@ -905,7 +1325,7 @@ impl Segment for MusicChangeSegment {
     }
 
     fn on_segment_start(&mut self, commands: &mut Commands, asset_server: &Res<AssetServer>, game: &mut ResMut<GameManager>, event_caller: &mut EventCaller) {
-        event_caller.music_change_event = Some(MusicChangeEvent(self.change_too.clone()));
+        event_caller.music_change_event = Some(MusicChangeEvent::to(self.change_too));
         self.music_changed = true;
     }
 }
\ No newline at end of file
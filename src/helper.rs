@@ -10,7 +10,11 @@ impl Plugin for HelperPlugin {
     fn build(&self, app: &mut App) {
         app
             .init_resource::<GameHelper>()
-            .add_system_to_stage(CoreStage::PreUpdate, update_mouse_world_pos);
+            .init_resource::<VirtualScreen>()
+            .add_startup_system(spawn_letterbox)
+            .add_system_to_stage(CoreStage::PreUpdate, fit_virtual_screen.before(PointerUpdate))
+            .add_system_to_stage(CoreStage::PreUpdate, update_mouse_world_pos.label(PointerUpdate))
+            .add_system_to_stage(CoreStage::PreUpdate, find_top_menu.label(PointerUpdate));
     }
 }
 
@@ -20,17 +24,41 @@ impl Plugin for HelperPlugin {
 
 #[derive(Default)]
 pub struct GameHelper {
-    mouse_world_pos : Vec2
+    mouse_world_pos : Vec2,
+    top_menu : Option<Entity>,
 }
 
 impl GameHelper {
     pub fn mouse_world_pos(&self) -> Vec2 {
         self.mouse_world_pos
     }
+
+    // lets the keyboard and gamepad cursor stand in for the mouse
+    pub fn set_mouse_world_pos(&mut self, pos: Vec2) {
+        self.mouse_world_pos = pos;
+    }
+
+    /// While a menu is open, the kitchen underneath it doesn't take any clicks.
+    pub fn is_menu_open(&self) -> bool {
+        self.top_menu.is_some()
+    }
+
+    /// Whether `menu` is drawn over every other open menu, and so is the one that gets the clicks.
+    pub fn is_top_menu(&self, menu: Entity) -> bool {
+        self.top_menu == Some(menu)
+    }
 }
 
+/// The root of a menu drawn over the kitchen.
+#[derive(Component)]
+pub struct ModalMenu;
+
+#[derive(SystemLabel, Clone, Hash, Debug, PartialEq, Eq)]
+pub struct PointerUpdate;
+
 fn update_mouse_world_pos(
     mut game_info : ResMut<GameHelper>,
+    screen : Res<VirtualScreen>,
     windows : Res<Windows>,
     cam : Query<(&Camera, &GlobalTransform)>
 ) {
@@ -43,11 +71,106 @@ fn update_mouse_world_pos(
     };
 
     if let Some(screen_pos) = wnd.cursor_position() {
-        let window_size = Vec2::new(wnd.width() as f32, wnd.height() as f32);
-        let ndc = (screen_pos / window_size) * 2.0 - Vec2::ONE;
-        let ndc_to_world = camera_transform.compute_matrix() * camera.projection_matrix().inverse();
-        let world_pos = ndc_to_world.project_point3(ndc.extend(-1.0));
-        game_info.mouse_world_pos = world_pos.truncate();
+        let scale_factor = wnd.scale_factor() as f32;
+        let physical_size = Vec2::new(wnd.physical_width() as f32, wnd.physical_height() as f32);
+        let from_center = screen_pos * scale_factor - physical_size / 2.;
+        game_info.mouse_world_pos = from_center / screen.scale() + camera_transform.translation().truncate();
+    }
+}
+
+fn find_top_menu(
+    mut game_info : ResMut<GameHelper>,
+    menus : Query<(Entity, &Transform), With<ModalMenu>>,
+) {
+    game_info.top_menu = menus.iter()
+        .max_by(|(_, a), (_, b)| a.translation.z.total_cmp(&b.translation.z))
+        .map(|(entity, _)| entity);
+}
+
+//==================================================================================================
+//                          Virtual Screen
+//==================================================================================================
+
+/// Everything is laid out in this many world units, whatever the size of the window.
+pub const VIRTUAL_SIZE: Vec2 = Vec2::new(1280., 720.);
+
+// far enough out to cover any window, close enough to the camera to be drawn over everything
+const LETTERBOX_SIZE: f32 = 10000.;
+const LETTERBOX_LEVEL: f32 = 990.;
+
+/// How many physical pixels a world unit takes up. Always a whole number of pixels per pixel of
+/// art, so the art stays crisp, and whatever doesn't fit is letterboxed.
+pub struct VirtualScreen {
+    scale: f32,
+    // physical pixels per logical pixel, the projection depends on it as well as the scale
+    scale_factor: f32,
+}
+
+impl Default for VirtualScreen {
+    fn default() -> Self {
+        VirtualScreen { scale: 1., scale_factor: 1. }
+    }
+}
+
+impl VirtualScreen {
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    fn fit(physical_size: Vec2) -> f32 {
+        let fit = (physical_size / VIRTUAL_SIZE).min_element();
+        (fit * DEFAULT_SPRITE_SCALING).floor().max(1.) / DEFAULT_SPRITE_SCALING
+    }
+}
+
+#[derive(Component)]
+pub struct Letterbox;
+
+fn spawn_letterbox(mut commands: Commands) {
+    let half = VIRTUAL_SIZE / 2. + LETTERBOX_SIZE / 2.;
+    let bars = [
+        (Vec2::new(-half.x, 0.), "Letterbox Left"),
+        (Vec2::new(half.x, 0.), "Letterbox Right"),
+        (Vec2::new(0., half.y), "Letterbox Top"),
+        (Vec2::new(0., -half.y), "Letterbox Bottom"),
+    ];
+
+    for (pos, name) in bars {
+        commands.spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: Color::BLACK,
+                custom_size: Some(Vec2::splat(LETTERBOX_SIZE)),
+                ..default()
+            },
+            transform: Transform::from_xyz(pos.x, pos.y, LETTERBOX_LEVEL),
+            ..default()
+        })
+            .insert(Letterbox)
+            .insert(Name::new(name));
+    }
+}
+
+fn fit_virtual_screen(
+    mut screen : ResMut<VirtualScreen>,
+    windows : Res<Windows>,
+    mut cameras : Query<&mut OrthographicProjection>,
+) {
+    let wnd = match windows.get_primary() {
+        Some(wnd) => wnd,
+        None => return,
+    };
+
+    let scale = VirtualScreen::fit(Vec2::new(wnd.physical_width() as f32, wnd.physical_height() as f32));
+    let scale_factor = wnd.scale_factor() as f32;
+    if scale == screen.scale && scale_factor == screen.scale_factor {
+        return;
+    }
+
+    screen.scale = scale;
+    screen.scale_factor = scale_factor;
+    // the projection works in logical pixels
+    for mut projection in cameras.iter_mut() {
+        projection.scale = scale_factor / scale;
     }
 }
 
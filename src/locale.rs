@@ -0,0 +1,146 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use crate::accessibility::AccessibilitySettings;
+use crate::element::Element;
+
+pub const PIXEL_FONT: &str = "fonts/pixel_font.ttf";
+// the pixel font only has plain ascii, this one covers accents, greek and cyrillic too
+pub const FALLBACK_FONT: &str = "fonts/JetBrainsMono.ttf";
+
+pub struct LocalePlugin;
+
+impl Plugin for LocalePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(Localization::new(Language::English))
+            .add_event::<ChangeLanguageEvent>()
+            .add_system(change_language)
+            .add_system_to_stage(CoreStage::PostUpdate, fall_back_font);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    English,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+    /// The language's own name for itself, so it can be found in the picker.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+        }
+    }
+
+    /// English is written straight into the game, every other language is a file of `key = value` lines.
+    fn file(&self) -> &'static str {
+        match self {
+            Language::English => "",
+            Language::Spanish => include_str!("../assets/locale/es.lang"),
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let index = Language::ALL.iter().position(|language| *language == self).unwrap_or(0);
+        Language::ALL[(index + 1) % Language::ALL.len()]
+    }
+
+    fn uses_pixel_font(&self) -> bool {
+        *self == Language::English
+    }
+}
+
+/// Parses `key = value` lines. Blank lines and lines starting with `#` are skipped, and `\n` in a
+/// value becomes a line break.
+pub fn parse_lang(file: &str) -> HashMap<String, String> {
+    file.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once(" = "))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().replace("\\n", "\n")))
+        .collect()
+}
+
+/// Every bit of text shown to the player goes through here. Dialogue and other lines are keyed by
+/// their english text, elements by their id. Anything missing falls back to english.
+pub struct Localization {
+    language: Language,
+    strings: HashMap<String, String>,
+}
+
+impl Localization {
+    pub fn new(language: Language) -> Self {
+        Localization {
+            language,
+            strings: parse_lang(language.file()),
+        }
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    pub fn text<'a>(&'a self, english: &'a str) -> &'a str {
+        self.strings.get(english.trim()).map_or(english, String::as_str)
+    }
+
+    /// Translates a line with `{}` gaps, then fills them in order. The gaps can be moved around in
+    /// the translation, but not reordered.
+    pub fn format(&self, english: &str, args: &[&str]) -> String {
+        args.iter().fold(self.text(english).to_string(), |line, arg| line.replacen("{}", arg, 1))
+    }
+
+    pub fn element_name(&self, element: &Element) -> &str {
+        self.strings.get(&format!("element.{}.name", element.id)).map_or(element.name, String::as_str)
+    }
+
+    pub fn element_desc(&self, element: &Element) -> &str {
+        self.strings.get(&format!("element.{}.desc", element.id)).map_or(element.desc, String::as_str)
+    }
+}
+
+pub struct ChangeLanguageEvent(pub Language);
+
+//==================================================================================================
+//                          Systems
+//==================================================================================================
+
+fn change_language(
+    mut localization: ResMut<Localization>,
+    mut change_language_event: EventReader<ChangeLanguageEvent>,
+) {
+    if let Some(event) = change_language_event.iter().last() {
+        if localization.language != event.0 {
+            *localization = Localization::new(event.0);
+        }
+    }
+}
+
+/// Swaps every pixel font text over to the fallback font for languages the pixel font can't write,
+/// or when the readable font is picked in the accessibility settings.
+fn fall_back_font(
+    asset_server: Res<AssetServer>,
+    localization: Res<Localization>,
+    accessibility: Res<AccessibilitySettings>,
+    mut texts: Query<&mut Text>,
+) {
+    let pixel_font: Handle<Font> = asset_server.load(PIXEL_FONT);
+    let fallback_font: Handle<Font> = asset_server.load(FALLBACK_FONT);
+    let (from, to) = if localization.language.uses_pixel_font() && !accessibility.readable_font {
+        (fallback_font, pixel_font)
+    } else {
+        (pixel_font, fallback_font)
+    };
+
+    for mut text in texts.iter_mut() {
+        if text.sections.iter().any(|section| section.style.font == from) {
+            for section in text.sections.iter_mut().filter(|section| section.style.font == from) {
+                section.style.font = to.clone();
+            }
+        }
+    }
+}
@@ -12,6 +12,11 @@ mod page;
 mod boss_fight;
 mod gameflow;
 mod audio;
+mod focus;
+mod difficulty;
+mod plating;
+mod locale;
+mod accessibility;
 
 use std::time::Duration;
 use bevy::prelude::*;
@@ -25,11 +30,15 @@ use bevy_prototype_debug_lines::DebugLinesPlugin;
 use crate::boss_fight::BossFightPlugin;
 use crate::game::GamePlugin;
 use crate::gameflow::GameflowPlugin;
-use crate::helper::{GameHelper, HelperPlugin};
+use crate::helper::{GameHelper, HelperPlugin, VIRTUAL_SIZE};
 use crate::npc::NpcPlugin;
 use crate::page::PagePlugin;
 use crate::quest::{QuestPlugin};
 use crate::audio::AudioPlugin;
+use crate::focus::FocusPlugin;
+use crate::difficulty::DifficultyPlugin;
+use crate::locale::LocalePlugin;
+use crate::accessibility::AccessibilityPlugin;
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum AppState {
@@ -41,9 +50,10 @@ fn main() {
         .insert_resource(ClearColor(Color::hex("183f39").unwrap()))
         .add_state(Game)
         .insert_resource(WindowDescriptor {
-            width: 1280.0,
-            height: 720.0,
+            width: VIRTUAL_SIZE.x,
+            height: VIRTUAL_SIZE.y,
             title: "Fantastical Kitchen".to_string(),
+            fit_canvas_to_parent: true,
             ..default()
         })
         .insert_resource(ImageSettings::default_nearest())
@@ -60,6 +70,10 @@ fn main() {
         .add_plugin(PagePlugin)
         .add_plugin(BossFightPlugin)
         .add_plugin(AudioPlugin)
+        .add_plugin(FocusPlugin)
+        .add_plugin(DifficultyPlugin)
+        .add_plugin(LocalePlugin)
+        .add_plugin(AccessibilityPlugin)
         .add_startup_system(setup_camera)
         .run();
 }
@@ -62,10 +62,5 @@ impl MixerRecipe {
 }
 
 pub fn get_result(element_a: Element, element_b: Element, registry: &Res<Registry>) -> Option<Element> {
-    let iden = MixerRecipeIden::new(element_a, element_b);
-    if let Some(mr) = registry.mixer_recipe_registry.get(&iden) {
-        Some(mr.result.clone())
-    } else {
-        None
-    }
+    registry.craft(MIXER, &[element_a, element_b])
 }
\ No newline at end of file
@@ -1,5 +1,9 @@
 mod squee;
+pub mod animation;
+pub mod patience;
+pub mod reaction;
 
+use std::collections::VecDeque;
 use bevy::ecs::system::Command;
 use bevy::math::Vec2Swizzles;
 use bevy::prelude::*;
@@ -8,12 +12,20 @@ use bevy_inspector_egui::egui::FontSelection::Style;
 use bevy_prototype_debug_lines::DebugLines;
 use imagesize::size;
 use crate::{AppState, GameHelper};
+use crate::focus::Focusable;
 use crate::audio::SayEvent;
-use crate::element::Element;
+use crate::audio::music::MusicTrack;
+use crate::npc::animation::{animate_npc, NPC_FRAME_SIZE, NpcAnimations, NpcAnimationState, NpcAnimator, PlayAnimation};
+use crate::npc::patience::{drain_patience, Mood, Patience, PatienceRanOutEvent, spawn_patience_meter, update_mood_expression, update_patience_meters};
+use crate::npc::reaction::Reactions;
+use crate::page::MovingTo;
+use crate::element::{Element, Tag};
 use crate::game::{GameManager, GameStatus};
 use crate::game::GameStatus::QuestComplete;
+use crate::accessibility::AccessibilitySettings;
+use crate::locale::Localization;
 use crate::quest::Quest;
-use crate::ui::{DropElementEvent, NPC_LEVEL, Rect, Slot, TitleText};
+use crate::ui::{DropElementEvent, NPC_LEVEL, Rect, TitleText};
 
 pub struct NpcPlugin;
 
@@ -22,11 +34,17 @@ impl Plugin for NpcPlugin {
         app
             .add_event::<NpcClickEvent>()
             .add_event::<NPCDropEvent>()
+            .add_event::<PatienceRanOutEvent>()
             .init_resource::<NPCData>()
             .add_startup_system(setup_npc_assets)
             .add_system(click_npc)
             .add_system(on_npc_hover)
             .add_system(dialogue)
+            .add_system(animate_npc)
+            .add_system(transition_npc)
+            .add_system(drain_patience)
+            .add_system(update_mood_expression)
+            .add_system(update_patience_meters)
             .add_system(on_npc_drop);
     }
 }
@@ -37,20 +55,14 @@ pub struct Npc {
     pub name: String,
     pub sprite: Handle<Image>,
     pub sprite_path: String,
-    pub talking_anims: Vec<Handle<Image>>,
-    pub talking_index: usize,
+    pub animations: NpcAnimations,
+    pub reactions: Reactions,
+    // faded to when the npc walks in, the music carries on as it is without one
+    pub theme: Option<MusicTrack>,
     // pub color: Color,
     // pub voice: Handle<Audio>,
 }
 
-impl Npc {
-    pub fn talk_frame(&mut self) -> usize {
-        self.talking_index += 1;
-        let i = &self.talking_index % 2;
-        return i
-    }
-}
-
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub enum NpcKind {
     Squee,
@@ -63,9 +75,66 @@ pub enum NpcKind {
 //                  Setup
 //==================================================================================================
 
+pub const MAIN_SPOT: usize = 0;
+const OFF_SCREEN_X: f32 = 800.;
+
+/// Where a customer stands at the counter, how big they are drawn and where their text goes.
+#[derive(Clone, Copy)]
+pub struct SpotLayout {
+    pub pos: Vec2,
+    pub scale: f32,
+    pub text_pos: Vec2,
+    pub text_width: f32,
+}
+
+impl SpotLayout {
+    pub const fn new(pos: Vec2, scale: f32, text_pos: Vec2, text_width: f32) -> Self {
+        SpotLayout {
+            pos,
+            scale,
+            text_pos,
+            text_width,
+        }
+    }
+
+    pub fn home(&self) -> Transform {
+        Transform::from_xyz(self.pos.x, self.pos.y, NPC_LEVEL)
+    }
+
+    pub fn off_screen(&self) -> Transform {
+        Transform::from_xyz(OFF_SCREEN_X, self.pos.y, NPC_LEVEL)
+    }
+
+    fn sprite(&self) -> TextureAtlasSprite {
+        TextureAtlasSprite {
+            custom_size: Some(NPC_FRAME_SIZE * self.scale),
+            ..default()
+        }
+    }
+
+    fn text_transform(&self) -> Transform {
+        Transform::from_xyz(self.text_pos.x, self.text_pos.y, NPC_LEVEL)
+    }
+
+    fn text_bounds(&self) -> Text2dBounds {
+        Text2dBounds {
+            size: Vec2::new(self.text_width, 4000.)
+        }
+    }
+}
+
+pub const STORY_SPOT: SpotLayout = SpotLayout::new(Vec2::new(384., 136.), 8., Vec2::new(206.5, 280.), 400.);
+
+pub const RUSH_SPOTS: [SpotLayout; 3] = [
+    SpotLayout::new(Vec2::new(250., 40.), 5., Vec2::new(180., 280.), 140.),
+    SpotLayout::new(Vec2::new(400., 40.), 5., Vec2::new(330., 280.), 140.),
+    SpotLayout::new(Vec2::new(550., 40.), 5., Vec2::new(480., 280.), 140.),
+];
+
 fn setup_npc_assets(
     mut commands : Commands,
     asset_server : Res<AssetServer>,
+    mut atlases : ResMut<Assets<TextureAtlas>>,
     mut game : ResMut<GameManager>
 ) {
     //Squee
@@ -74,11 +143,14 @@ fn setup_npc_assets(
         name: "Squee the Thumbless".to_string(),
         sprite: asset_server.load("sprites/squee.png"),
         sprite_path: "sprites/squee.png".to_string(),
-        talking_anims: vec![
-            asset_server.load("sprites/squee_talk1.png"),
-            asset_server.load("sprites/squee_talk2.png"),
-        ],
-        talking_index: 0,
+        animations: NpcAnimations::from_sheet(&asset_server, &mut atlases, "sprites/Squee_tilesheet.png"),
+        reactions: Reactions::default()
+            .with_wrong_line("Squee doesnt think that is right.")
+            .with_close_line("Ooh, almost! Squee can smell it!")
+            .with_hated(&Element::MAGMA_PEPPER, "Hot hot HOT! Keep that away from Squee!")
+            .with_loved_tag(Tag::Cold, "Ooh, nice and cold. Squee likes cold things!")
+            .with_hated_tag(Tag::Hot, "Too hot for Squee! Goblins have delicate tongues."),
+        theme: Some(MusicTrack::TAVERN),
     };
 
     let conrad1 = Npc {
@@ -86,11 +158,9 @@ fn setup_npc_assets(
         name: "Sir Conrad".to_string(),
         sprite: asset_server.load("sprites/sir_conrad.png"),
         sprite_path: "sprites/knight.png".to_string(),
-        talking_anims: vec![
-            asset_server.load("sprites/sir_conrad_talk_1.png"),
-            asset_server.load("sprites/sir_conrad_talk_2.png"),
-        ],
-        talking_index: 0,
+        animations: NpcAnimations::from_sheet(&asset_server, &mut atlases, "sprites/conrad_tilesheet.png"),
+        reactions: conrad_reactions(),
+        theme: Some(MusicTrack::BACKGROUND),
     };
 
     let pumpkinhead = Npc {
@@ -98,11 +168,13 @@ fn setup_npc_assets(
         name: "Pumpkinhead".to_string(),
         sprite: asset_server.load("sprites/pumpkinhead.png"),
         sprite_path: "sprites/pumpkinhead.png".to_string(),
-        talking_anims: vec![
-            asset_server.load("sprites/pumpkinhead_talk_1.png"),
-            asset_server.load("sprites/pumpkinhead_talk_2.png"),
-        ],
-        talking_index: 0,
+        animations: NpcAnimations::from_sheet(&asset_server, &mut atlases, "sprites/Gourdon_tilesheet.png"),
+        reactions: Reactions::default()
+            .with_wrong_line("... um ... that isnt what I asked for ...")
+            .with_close_line("... oh ... that is almost it ...")
+            .with_hated(&Element::RAW_PORK, "... please dont give me back my own pig ...")
+            .with_hated_tag(Tag::Meat, "... I dont eat meat ... I raise pigs, I dont eat them ..."),
+        theme: None,
     };
 
     let conrad2 = Npc {
@@ -110,11 +182,9 @@ fn setup_npc_assets(
         name: "Sir Conrad".to_string(),
         sprite: asset_server.load("sprites/sir_conrad.png"),
         sprite_path: "sprites/knight.png".to_string(),
-        talking_anims: vec![
-            asset_server.load("sprites/sir_conrad_talk_1.png"),
-            asset_server.load("sprites/sir_conrad_talk_2.png"),
-        ],
-        talking_index: 0,
+        animations: NpcAnimations::from_sheet(&asset_server, &mut atlases, "sprites/conrad_tilesheet.png"),
+        reactions: conrad_reactions(),
+        theme: Some(MusicTrack::BACKGROUND),
     };
 
     let gordon = Npc {
@@ -122,11 +192,14 @@ fn setup_npc_assets(
         name: "Gordon Gamsey".to_string(),
         sprite: asset_server.load("sprites/gordon.png"),
         sprite_path: "sprites/gordon.png".to_string(),
-        talking_anims: vec![
-            asset_server.load("sprites/gordon_talk_1.png"),
-            asset_server.load("sprites/gordon_talk_2.png"),
-        ],
-        talking_index: 0,
+        animations: NpcAnimations::from_sheet(&asset_server, &mut atlases, "sprites/Gordo_tilesheet.png"),
+        reactions: Reactions::default()
+            .with_wrong_line("What is this rubbish? That is not what I ordered!")
+            .with_close_line("Closer. Still not good enough.")
+            .with_hated(&Element::YETI_WATER, "You bring me water? WATER?!")
+            .with_hated(&Element::RAW_PORK, "It is BLOODY RAW!")
+            .with_hated_tag(Tag::Raw, "Did you even cook this? It is RAW!"),
+        theme: Some(MusicTrack::BOSS),
     };
 
     game.npc_data.npcs.push(squee);
@@ -135,22 +208,35 @@ fn setup_npc_assets(
     game.npc_data.npcs.push(conrad2);
     game.npc_data.npcs.push(gordon);
 
+    let font = asset_server.load("fonts/pixel_font.ttf");
+    let atlas = game.npc_data.npcs[0].animations.atlas.clone();
+    let spot = spawn_counter_spot(&mut commands, font, atlas, MAIN_SPOT, STORY_SPOT, Some(0));
+    game.npc_data.spots.push(spot);
+}
+
+fn spawn_counter_spot(
+    commands : &mut Commands,
+    font : Handle<Font>,
+    atlas : Handle<TextureAtlas>,
+    index : usize,
+    layout : SpotLayout,
+    npc : Option<usize>,
+) -> CounterSpot {
     // NPC Sprite
-    let npc_sprite = commands.spawn_bundle(SpriteBundle {
-        sprite: Sprite {
-            custom_size: Some(Vec2::new(28. * 8., 38. * 8.)),
-            ..default()
-        },
-        transform: Transform::from_xyz(384., 136., NPC_LEVEL),
-        texture: asset_server.load("sprites/squee.png"),
+    let npc_sprite = commands.spawn_bundle(SpriteSheetBundle {
+        sprite: layout.sprite(),
+        transform: layout.home(),
+        texture_atlas: atlas,
         ..default()
     })
         .insert(NpcSprite)
+        .insert(Focusable::default())
+        .insert(SpotIndex(index))
+        .insert(NpcAnimator::default())
         .insert(Name::new("NpcSprite"))
         .id();
 
     // NPC Text Box
-    let font = asset_server.load("fonts/pixel_font.ttf");
     // todo: change
     let text_style = TextStyle {
         font,
@@ -164,37 +250,66 @@ fn setup_npc_assets(
 
     let npc_text_box = commands.spawn_bundle(Text2dBundle {
         text: Text::from_section("", text_style).with_alignment(text_alignment),
-        transform: Transform::from_xyz(206.5, 280., NPC_LEVEL),
-        text_2d_bounds: Text2dBounds {
-            size: Vec2::new(400., 4000.)
-        },
+        transform: layout.text_transform(),
+        text_2d_bounds: layout.text_bounds(),
         ..default()
     })
         .insert(NpcText)
+        .insert(SpotIndex(index))
         .insert(Name::new("Npc Text")).id();
 
-    game.npc_data.npc_sprite = Some(npc_sprite);
-    game.npc_data.npc_dialog_box = Some(npc_text_box);
+    let (meter, meter_fill) = spawn_patience_meter(commands, SpotIndex(index));
+
+    CounterSpot {
+        npc,
+        order: None,
+        patience: None,
+        layout,
+        sprite: npc_sprite,
+        dialog_box: npc_text_box,
+        meter,
+        meter_fill,
+    }
+}
+
+fn conrad_reactions() -> Reactions {
+    Reactions::default()
+        .with_wrong_line("A fine offering, but not what this knight requested.")
+        .with_close_line("We are close to victory! Press on!")
+        .with_loved(&Element::BACON, "Bacon! The noblest of meats!")
 }
 
 //==================================================================================================
 //                  NPC Data
 //==================================================================================================
 
+pub struct CounterSpot {
+    pub npc : Option<usize>,
+    pub order : Option<Element>,
+    pub patience : Option<Patience>,
+    layout : SpotLayout,
+    sprite : Entity,
+    dialog_box : Entity,
+    meter : Entity,
+    meter_fill : Entity,
+}
+
 pub struct NPCData {
     npcs : Vec<Npc>,
-    current_npc : usize,
-    npc_dialog_box : Option<Entity>,
-    npc_sprite : Option<Entity>,
+    spots : Vec<CounterSpot>,
+    queue : VecDeque<(usize, Element, Option<f32>)>,
+    speaker : usize,
+    in_transition : bool,
 }
 
 impl Default for NPCData {
     fn default() -> Self {
         NPCData {
             npcs : Vec::new(),
-            current_npc: 0,
-            npc_dialog_box : None,
-            npc_sprite : None
+            spots : Vec::new(),
+            queue : VecDeque::new(),
+            speaker : MAIN_SPOT,
+            in_transition : false,
         }
     }
 }
@@ -202,27 +317,200 @@ impl Default for NPCData {
 impl NPCData {
 
     pub fn get_current_npc(&self) -> Option<&Npc> {
-        self.npcs.get(self.current_npc)
+        self.npc_at(MAIN_SPOT)
     }
 
     pub fn get_current_npc_mut(&mut self) -> Option<&mut Npc> {
-        self.npcs.get_mut(self.current_npc)
+        let index = self.spots.get(MAIN_SPOT)?.npc?;
+        self.npcs.get_mut(index)
+    }
+
+    pub fn npc(&self, index : usize) -> Option<&Npc> {
+        self.npcs.get(index)
+    }
+
+    pub fn npc_at(&self, spot : usize) -> Option<&Npc> {
+        let index = self.spots.get(spot)?.npc?;
+        self.npcs.get(index)
+    }
+
+    /// Who walks in next time the story swaps the npc at the counter.
+    pub fn next_story_npc(&self) -> Option<&Npc> {
+        let index = self.spots.get(MAIN_SPOT)?.npc.map_or(0, |npc| npc + 1);
+        self.npcs.get(index)
+    }
+
+    pub fn spot(&self, spot : usize) -> Option<&CounterSpot> {
+        self.spots.get(spot)
+    }
+
+    pub fn spot_count(&self) -> usize {
+        self.spots.len()
     }
 
-    pub fn say(&self, commands : &mut Commands, message : &str) -> f64 {
-        if let Some(text_box) = self.npc_dialog_box {
+    pub fn npc_count(&self) -> usize {
+        self.npcs.len()
+    }
+
+    /// The first npc of this kind in the story.
+    pub fn index_of(&self, kind : &NpcKind) -> Option<usize> {
+        self.npcs.iter().position(|npc| npc.kind == *kind)
+    }
+
+    /// The spot of whoever spoke last, used to pick the voice to play.
+    pub fn speaker(&self) -> usize {
+        self.speaker
+    }
+
+    pub fn say(&mut self, commands : &mut Commands, message : &str) -> f64 {
+        self.say_at(commands, MAIN_SPOT, message)
+    }
+
+    pub fn say_at(&mut self, commands : &mut Commands, spot : usize, message : &str) -> f64 {
+        if let Some(spot_data) = self.spots.get(spot) {
             let say = Say::new(message);
             let length = say.duration;
-            commands.entity(text_box).insert(Say::new(message));
+            commands.entity(spot_data.dialog_box).insert(say);
+            self.speaker = spot;
 
-            return length;
+            length
         } else {
-            return 0.;
+            0.
+        }
+    }
+
+    pub fn emote(&self, commands : &mut Commands, emotion : NpcAnimationState) {
+        self.emote_at(commands, MAIN_SPOT, emotion)
+    }
+
+    pub fn emote_at(&self, commands : &mut Commands, spot : usize, emotion : NpcAnimationState) {
+        if let Some(spot) = self.spots.get(spot) {
+            commands.entity(spot.sprite).insert(PlayAnimation(emotion));
         }
     }
 
     pub fn spawn_next_npc(&mut self) {
-        self.current_npc += 1;
+        if let Some(spot) = self.spots.get_mut(MAIN_SPOT) {
+            spot.npc = Some(spot.npc.map_or(0, |npc| npc + 1));
+        }
+    }
+
+    /// Walks the current npc off screen, brings the next one in and has them say `line` once they
+    /// are back at the counter.
+    pub fn swap_npc(&mut self, commands : &mut Commands, line : &str) {
+        if self.start_leaving(commands, MAIN_SPOT, NextCustomer::Story, Some(line.to_string())) {
+            self.in_transition = true;
+        } else {
+            self.spawn_next_npc();
+        }
+    }
+
+    pub fn is_transitioning(&self) -> bool {
+        self.in_transition
+    }
+
+    /// Lines a customer up behind the counter, they get a spot once one frees up. With `patience`
+    /// set they only wait that many seconds at the counter before giving up.
+    pub fn enqueue(&mut self, npc : usize, order : Element, patience : Option<f32>) {
+        self.queue.push_back((npc, order, patience));
+    }
+
+    pub fn start_patience(&mut self, spot : usize, seconds : f32) {
+        if let Some(spot) = self.spots.get_mut(spot) {
+            spot.patience = Some(Patience::lasting(seconds));
+        }
+    }
+
+    /// Freezes the patience at `spot`, the mood it ended on sticks around until the npc leaves.
+    pub fn stop_patience(&mut self, spot : usize) {
+        if let Some(patience) = self.spots.get_mut(spot).and_then(|spot| spot.patience.as_mut()) {
+            patience.stop();
+        }
+    }
+
+    pub fn fail_handoff(&mut self, spot : usize) {
+        if let Some(patience) = self.spots.get_mut(spot).and_then(|spot| spot.patience.as_mut()) {
+            patience.fail();
+        }
+    }
+
+    /// Whether the customer at `spot` ran out of patience before their order was done.
+    pub fn ran_out_at(&self, spot : usize) -> bool {
+        self.spots.get(spot)
+            .and_then(|spot| spot.patience.as_ref())
+            .is_some_and(|patience| patience.is_empty())
+    }
+
+    pub fn mood_at(&self, spot : usize) -> Mood {
+        self.spots.get(spot)
+            .and_then(|spot| spot.patience.as_ref())
+            .map_or(Mood::Content, |patience| patience.mood())
+    }
+
+    /// Sends whoever is at `spot` away, the next customer in the queue takes their place.
+    pub fn send_away(&mut self, commands : &mut Commands, spot : usize) {
+        self.start_leaving(commands, spot, NextCustomer::Queue, None);
+    }
+
+    fn start_leaving(&mut self, commands : &mut Commands, spot : usize, next : NextCustomer, line : Option<String>) -> bool {
+        if let Some(spot_data) = self.spots.get_mut(spot) {
+            spot_data.order = None;
+            spot_data.patience = None;
+            commands.entity(spot_data.sprite)
+                .insert(PlayAnimation(NpcAnimationState::Leave))
+                .insert(MovingTo(spot_data.layout.off_screen()))
+                .insert(NpcTransition {
+                    stage: TransitionStage::Leaving,
+                    next,
+                    line,
+                });
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Opens up to `count` spots at the counter, squeezing the main spot over to make room.
+    pub fn open_spots(&mut self, commands : &mut Commands, asset_server : &AssetServer, count : usize) {
+        let count = count.clamp(1, RUSH_SPOTS.len());
+
+        for (index, layout) in RUSH_SPOTS.iter().take(count).enumerate() {
+            if let Some(spot) = self.spots.get_mut(index) {
+                spot.layout = *layout;
+                commands.entity(spot.sprite)
+                    .insert(layout.sprite())
+                    .insert(MovingTo(layout.home()));
+                commands.entity(spot.dialog_box)
+                    .insert(layout.text_transform())
+                    .insert(layout.text_bounds());
+            } else if let Some(npc) = self.npcs.first() {
+                let font = asset_server.load("fonts/pixel_font.ttf");
+                let spot = spawn_counter_spot(commands, font, npc.animations.atlas.clone(), index, *layout, None);
+                commands.entity(spot.sprite).insert(layout.off_screen());
+                self.spots.push(spot);
+            }
+        }
+    }
+
+    /// Closes every spot but the main one and moves it back to where the story has it.
+    pub fn close_spots(&mut self, commands : &mut Commands) {
+        for spot in self.spots.drain(1..) {
+            commands.entity(spot.sprite).despawn_recursive();
+            commands.entity(spot.dialog_box).despawn_recursive();
+            commands.entity(spot.meter).despawn_recursive();
+            commands.entity(spot.meter_fill).despawn_recursive();
+        }
+
+        if let Some(spot) = self.spots.get_mut(MAIN_SPOT) {
+            spot.layout = STORY_SPOT;
+            commands.entity(spot.sprite)
+                .insert(STORY_SPOT.sprite())
+                .insert(MovingTo(STORY_SPOT.home()));
+            commands.entity(spot.dialog_box)
+                .insert(STORY_SPOT.text_transform())
+                .insert(STORY_SPOT.text_bounds());
+        }
+        self.queue.clear();
     }
 }
 
@@ -237,31 +525,50 @@ pub struct Say {
     i: usize,
     start: f64,
     duration: f64,
+    is_localized: bool,
 }
 
 impl Say {
     const CHAR_SEC: f64 = 0.04;
 
     pub fn new(text: impl ToString) -> Self {
+        let text = text.to_string();
         Say {
-            text: text.to_string(),
+            duration: Say::duration_of(&text),
+            text,
             i: 0,
             start: -1.,
-            // gets the total duration of the text talking
-            duration: text
-                .to_string()
-                .chars()
-                .map(Say::char_duration)
-                .fold(0., |acc, x| acc + x)
-                * Say::CHAR_SEC, // todo: tweek
+            is_localized: false,
+        }
+    }
+
+    // gets the total duration of the text talking
+    fn duration_of(text: &str) -> f64 {
+        text.chars()
+            .map(Say::char_duration)
+            .fold(0., |acc, x| acc + x)
+            * Say::CHAR_SEC // todo: tweek
+    }
+
+    /// Swaps the line for its translation, returning whether it changed.
+    pub fn localize(&mut self, localization: &Localization) -> bool {
+        self.is_localized = true;
+        let text = localization.text(&self.text);
+        if text == self.text {
+            return false;
         }
+
+        self.text = text.to_string();
+        self.duration = Say::duration_of(&self.text);
+        true
     }
 
+    // pauses after full width and inverted punctuation too, but not before the opening ¡ and ¿
     fn char_duration(char: char) -> f64 {
         match char {
-            ' ' => 1.5,
-            ',' => 3.,
-            '.' | '!' | '?' => 6.,
+            ' ' | '\u{3000}' => 1.5,
+            ',' | ';' | ':' | '、' | '，' | '；' | '：' => 3.,
+            '.' | '!' | '?' | '…' | '。' | '！' | '？' => 6.,
             _ => 0.75,
         }
     }
@@ -288,83 +595,89 @@ pub struct NpcText;
 #[derive(Component)]
 pub struct NpcSprite;
 
+/// Which counter spot an npc sprite or text box belongs to.
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub struct SpotIndex(pub usize);
+
 fn dialogue(
     mut commands: Commands,
-    mut query_text: Query<(Entity, &mut Text, &mut Say), With<NpcText>>,
-    mut query_sprite: Query<(&mut Handle<Image>, &mut Sprite),  With<NpcSprite>>,
+    mut query_text: Query<(Entity, &mut Text, &mut Say, &SpotIndex), With<NpcText>>,
+    mut query_sprite: Query<(&mut NpcAnimator, &SpotIndex), With<NpcSprite>>,
     time: Res<Time>,
-    mut game : ResMut<GameManager>
+    game : Res<GameManager>,
+    localization: Res<Localization>,
+    accessibility: Res<AccessibilitySettings>,
+    mut say_event: EventWriter<SayEvent>,
     // audio: Res<Audio>
 ) {
-    let mut npc = game.npc_data.get_current_npc_mut();
-
-    if let Some(npc) = npc {
-        if let Ok((entity, mut text, mut say)) = query_text.get_single_mut() {
-            if let Ok((mut sprite_handle, mut sprite)) = query_sprite.get_single_mut() {
-                if say.i == 0 {
-                    say.start = time.seconds_since_startup();
-                    // change sprite picture
-                    *sprite_handle = npc.sprite.clone();
-                    // change sprite scaling
-                    let change_sprite_size = |width: f32, height: f32, mut sprite: &mut Sprite| {
-                        sprite.custom_size = Some(Vec2::new(28. * 8., 38. * 8.,));
-                    };
-                }
-
-                // compute the new i
-                let now = time.seconds_since_startup();
-                let mut new_i = say.compute_i(now);
-                // if we finished
-                if say.i >= say.text.len() {
-                    // and 1 sec has passed
+    for (entity, mut text, mut say, text_spot) in query_text.iter_mut() {
+        // the voice was started for the english line, so it needs to know how long the translation runs
+        if !say.is_localized && say.localize(&localization) {
+            say_event.send(SayEvent(say.duration));
+        }
 
-                    if now - say.duration - say.start > 1. {
-                        commands.entity(entity).remove::<Say>();
+        let npc = game.npc_data.npc_at(text_spot.0);
+        let animator = query_sprite.iter_mut().find(|(_, spot)| *spot == text_spot);
 
-                        // change sprite back to default sprite
-                        *sprite_handle = npc.sprite.clone();
-                    }
-                }
-                // if not finished
-                else if new_i != say.i {
-                    // there's new characters to say
-                    new_i = new_i.min(say.text.len());
-
-                    // sprite talking animation
-                    if new_i % 6 == 0 {
-                        let frame = npc.talk_frame();
-                        *sprite_handle = npc.talking_anims[frame].clone();
-                    }
+        if let (Some(npc), Some((mut animator, _))) = (npc, animator) {
+            if say.i == 0 {
+                say.start = time.seconds_since_startup();
+                animator.play(NpcAnimationState::Talk, &npc.animations);
+            }
 
-                    // magic line that updates the code by making the old text box equal to the new
-                    // sliced text box
-                    text.sections[0].value = say.text[0..new_i].to_string();
-                    // if i..new_i is not only spaces, produce a sound
-                    // if say.text[say.i..new_i].trim().len() > 0 {
-                    // audio.play(character.voice.clone());
-                    // }
-                    say.i = new_i;
+            // compute the new i
+            let now = time.seconds_since_startup();
+            let length = say.text.chars().count();
+            // without the typewriter the whole line shows up at once
+            let mut new_i = if accessibility.reduced_motion { length } else { say.compute_i(now) };
+            // if we finished
+            if say.i >= length {
+                animator.play(NpcAnimationState::Idle, &npc.animations);
+
+                // and 1 sec has passed
+                if now - say.duration - say.start > 1. {
+                    commands.entity(entity).remove::<Say>();
                 }
             }
+            // if not finished
+            else if new_i != say.i {
+                // there's new characters to say
+                new_i = new_i.min(length);
+
+                // magic line that updates the code by making the old text box equal to the new
+                // sliced text box
+                text.sections[0].value = say.text.chars().take(new_i).collect();
+                // if i..new_i is not only spaces, produce a sound
+                // if say.text[say.i..new_i].trim().len() > 0 {
+                // audio.play(character.voice.clone());
+                // }
+                say.i = new_i;
+            }
         }
     }
 }
 
-pub struct NpcClickEvent;
+pub struct NpcClickEvent(pub usize);
+
+pub fn npc_rect(transform: &GlobalTransform, sprite: &TextureAtlasSprite) -> Rect {
+    Rect::from_center(transform.translation().truncate(), sprite.custom_size.unwrap_or(NPC_FRAME_SIZE))
+}
 
 fn click_npc(
     game_helper: Res<GameHelper>,
     mut writer: EventWriter<NpcClickEvent>,
     mut lines : ResMut<DebugLines>,
-    mut query: Query<(&GlobalTransform, &Sprite), With<NpcSprite>>,
+    query: Query<(&GlobalTransform, &TextureAtlasSprite, &SpotIndex), With<NpcSprite>>,
+    game: Res<GameManager>,
     mouse : Res<Input<MouseButton>>,
 ) {
-    if let Ok((transform, sprite)) = query.get_single_mut() {
-        let rect = Slot::generate_rect(transform, sprite);
+    for (transform, sprite, spot) in query.iter() {
+        let rect = npc_rect(transform, sprite);
 
         //rect.draw_rect(&mut lines, Color::RED);
-        if rect.is_within(game_helper.mouse_world_pos()) && mouse.just_pressed(MouseButton::Left) {
-            writer.send(NpcClickEvent);
+        let is_seated = game.npc_data.npc_at(spot.0).is_some();
+        if is_seated && !game_helper.is_menu_open() && rect.is_within(game_helper.mouse_world_pos()) && mouse.just_pressed(MouseButton::Left) {
+            writer.send(NpcClickEvent(spot.0));
         };
     }
 }
@@ -372,37 +685,122 @@ fn click_npc(
 fn on_npc_hover(
     game_helper: Res<GameHelper>,
     mut text_query: Query<(&mut Text, &mut Visibility), With<TitleText>>,
-    mut query: Query<(&GlobalTransform, &Sprite), With<NpcSprite>>,
-    mut game: Res<GameManager>,
+    query: Query<(&GlobalTransform, &TextureAtlasSprite, &SpotIndex), With<NpcSprite>>,
+    game: Res<GameManager>,
+    localization: Res<Localization>,
     mut lines : ResMut<DebugLines>,
 ) {
-    if let Ok((transform, sprite)) = query.get_single_mut() {
-        let rect = Slot::generate_rect(transform, sprite);
+    for (transform, sprite, spot) in query.iter() {
+        let rect = npc_rect(transform, sprite);
 
         if rect.is_within(game_helper.mouse_world_pos()) {
-            for (mut text, mut visibility) in text_query.iter_mut() {
-                let index = game.npc_data.current_npc;
-                visibility.is_visible = true;
-                text.sections[0].value = game.npc_data.npcs.get(index).unwrap().name.clone();
+            if let Some(npc) = game.npc_data.npc_at(spot.0) {
+                for (mut text, mut visibility) in text_query.iter_mut() {
+                    visibility.is_visible = true;
+                    text.sections[0].value = localization.text(&npc.name).to_string();
+                }
             }
         }
     }
 }
 
-pub struct NPCDropEvent(pub Element);
+/// An element handed to the customer standing at the given spot.
+pub struct NPCDropEvent(pub Element, pub usize);
 
 fn on_npc_drop (
-    mut query: Query<(&GlobalTransform, &Sprite), With<NpcSprite>>,
+    query: Query<(&GlobalTransform, &TextureAtlasSprite, &SpotIndex), With<NpcSprite>>,
+    game: Res<GameManager>,
     mut drop_event : EventReader<DropElementEvent>,
     mut npc_drop_event : EventWriter<NPCDropEvent>
 ) {
-    if let Ok((transform, sprite)) = query.get_single_mut() {
-        let rect = Slot::generate_rect(transform, sprite);
+    for event in drop_event.iter() {
+        for (transform, sprite, spot) in query.iter() {
+            let rect = npc_rect(transform, sprite);
+            let is_seated = game.npc_data.npc_at(spot.0).is_some();
 
-        for event in drop_event.iter() {
-            if rect.is_within(event.0) {
-                npc_drop_event.send(NPCDropEvent(event.1.clone()))
+            if is_seated && rect.is_within(event.0) {
+                npc_drop_event.send(NPCDropEvent(event.1.clone(), spot.0))
             }
         }
     }
-}
\ No newline at end of file
+}
+
+//==================================================================================================
+//                  NPC Transitions
+//==================================================================================================
+
+enum TransitionStage {
+    Leaving,
+    Entering,
+}
+
+/// Who walks in once the npc leaving a spot is off screen.
+enum NextCustomer {
+    Story,
+    Queue,
+}
+
+#[derive(Component)]
+pub struct NpcTransition {
+    stage: TransitionStage,
+    next: NextCustomer,
+    line: Option<String>,
+}
+
+fn transition_npc(
+    mut commands: Commands,
+    mut game: ResMut<GameManager>,
+    mut query: Query<(Entity, &mut NpcTransition, &NpcAnimator, &SpotIndex), (With<NpcSprite>, Without<MovingTo>)>,
+    mut text_query: Query<(Entity, &mut Text, &SpotIndex), With<NpcText>>,
+    localization: Res<Localization>,
+    mut say_event: EventWriter<SayEvent>,
+) {
+    for (entity, mut transition, animator, spot) in query.iter_mut() {
+        match transition.stage {
+            TransitionStage::Leaving => {
+                // the old npc is off screen, swap them out before walking back in
+                match transition.next {
+                    NextCustomer::Story => game.npc_data.spawn_next_npc(),
+                    NextCustomer::Queue => {
+                        let next = game.npc_data.queue.pop_front();
+                        if let Some(spot_data) = game.npc_data.spots.get_mut(spot.0) {
+                            spot_data.npc = None;
+                            if let Some((npc, order, patience)) = next {
+                                transition.line = Some(localization.format("One {} please!", &[localization.element_name(&order)]));
+                                spot_data.npc = Some(npc);
+                                spot_data.order = Some(order);
+                                spot_data.patience = patience.map(Patience::lasting);
+                            }
+                        }
+                    }
+                }
+
+                for (text_entity, mut text, text_spot) in text_query.iter_mut() {
+                    if text_spot == spot {
+                        commands.entity(text_entity).remove::<Say>();
+                        text.sections[0].value = String::new();
+                    }
+                }
+
+                if let Some(spot_data) = game.npc_data.spots.get(spot.0) {
+                    commands.entity(entity)
+                        .insert(PlayAnimation(NpcAnimationState::Enter))
+                        .insert(MovingTo(spot_data.layout.home()));
+                }
+                transition.stage = TransitionStage::Entering;
+            }
+            TransitionStage::Entering => {
+                if animator.state() != NpcAnimationState::Enter || animator.is_finished() {
+                    if let Some(line) = transition.line.take() {
+                        let duration = game.npc_data.say_at(&mut commands, spot.0, line.as_str());
+                        say_event.send(SayEvent(duration));
+                    }
+                    if let NextCustomer::Story = transition.next {
+                        game.npc_data.in_transition = false;
+                    }
+                    commands.entity(entity).remove::<NpcTransition>();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::time::Duration;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use crate::game::GameManager;
+use crate::npc::SpotIndex;
+
+//==================================================================================================
+//                  Animation Data
+//==================================================================================================
+
+// every npc tilesheet is a row of 28x38 frames: idle, talk 1, talk 2
+pub const NPC_FRAME_SIZE: Vec2 = Vec2::new(28., 38.);
+const NPC_SHEET_COLUMNS: usize = 3;
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum NpcAnimationState {
+    Idle,
+    Impatient,
+    Angry,
+    Talk,
+    Happy,
+    Disgusted,
+    Leave,
+    Enter,
+}
+
+#[derive(Clone, Debug)]
+pub struct AnimationClip {
+    pub frames: Vec<usize>,
+    pub frame_time: f32,
+    pub looping: bool,
+    pub color: Color,
+}
+
+impl AnimationClip {
+    pub fn new(frames: &[usize], frame_time: f32, looping: bool) -> Self {
+        AnimationClip {
+            frames: frames.to_vec(),
+            frame_time,
+            looping,
+            color: Color::WHITE,
+        }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+pub struct NpcAnimations {
+    pub atlas: Handle<TextureAtlas>,
+    clips: HashMap<NpcAnimationState, AnimationClip>,
+}
+
+impl NpcAnimations {
+    pub fn from_sheet(asset_server: &Res<AssetServer>, atlases: &mut ResMut<Assets<TextureAtlas>>, path: &str) -> Self {
+        let texture = asset_server.load(path);
+        let atlas = TextureAtlas::from_grid(texture, NPC_FRAME_SIZE, NPC_SHEET_COLUMNS, 1);
+
+        let mut clips = HashMap::default();
+        clips.insert(NpcAnimationState::Idle, AnimationClip::new(&[0], 1., true));
+        clips.insert(NpcAnimationState::Impatient, AnimationClip::new(&[0, 0, 0, 1], 0.3, true).with_color(Color::rgb(1.0, 0.9, 0.75)));
+        clips.insert(NpcAnimationState::Angry, AnimationClip::new(&[0, 1], 0.2, true).with_color(Color::rgb(1.0, 0.6, 0.55)));
+        clips.insert(NpcAnimationState::Talk, AnimationClip::new(&[1, 2], 0.15, true));
+        clips.insert(NpcAnimationState::Happy, AnimationClip::new(&[1, 0, 1, 0], 0.1, false));
+        clips.insert(NpcAnimationState::Disgusted, AnimationClip::new(&[0, 0, 0], 0.25, false).with_color(Color::rgb(0.7, 0.9, 0.6)));
+        clips.insert(NpcAnimationState::Leave, AnimationClip::new(&[0, 1, 0], 0.1, false));
+        clips.insert(NpcAnimationState::Enter, AnimationClip::new(&[0, 1, 0], 0.1, false));
+
+        NpcAnimations {
+            atlas: atlases.add(atlas),
+            clips,
+        }
+    }
+
+    pub fn clip(&self, state: NpcAnimationState) -> Option<&AnimationClip> {
+        self.clips.get(&state)
+    }
+}
+
+//==================================================================================================
+//                  Animator
+//==================================================================================================
+
+#[derive(Component)]
+pub struct NpcAnimator {
+    state: NpcAnimationState,
+    resume: NpcAnimationState,
+    frame: usize,
+    timer: Timer,
+    finished: bool,
+}
+
+impl Default for NpcAnimator {
+    fn default() -> Self {
+        NpcAnimator {
+            state: NpcAnimationState::Idle,
+            resume: NpcAnimationState::Idle,
+            frame: 0,
+            timer: Timer::new(Duration::from_secs(1), false),
+            finished: false,
+        }
+    }
+}
+
+impl NpcAnimator {
+    pub fn state(&self) -> NpcAnimationState {
+        self.state
+    }
+
+    pub fn resume(&self) -> NpcAnimationState {
+        self.resume
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    // looping states (idle, talk) are what the npc falls back to once a one shot clip is done
+    pub fn play(&mut self, state: NpcAnimationState, animations: &NpcAnimations) {
+        if let Some(clip) = animations.clip(state) {
+            if clip.looping {
+                if self.state == state && self.resume == state {
+                    return;
+                }
+                self.resume = state;
+                if !self.is_playing_one_shot(animations) {
+                    self.start(state, clip);
+                }
+            } else {
+                self.start(state, clip);
+            }
+        }
+    }
+
+    fn is_playing_one_shot(&self, animations: &NpcAnimations) -> bool {
+        let looping = animations.clip(self.state).map(|clip| clip.looping).unwrap_or(true);
+        !looping && !self.finished
+    }
+
+    fn start(&mut self, state: NpcAnimationState, clip: &AnimationClip) {
+        self.state = state;
+        self.frame = 0;
+        self.finished = false;
+        self.timer = Timer::new(Duration::from_secs_f32(clip.frame_time), true);
+    }
+}
+
+#[derive(Component)]
+pub struct PlayAnimation(pub NpcAnimationState);
+
+pub fn animate_npc(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut NpcAnimator, &mut TextureAtlasSprite, &mut Handle<TextureAtlas>, &mut Visibility, &SpotIndex, Option<&PlayAnimation>)>,
+    time: Res<Time>,
+    game: Res<GameManager>,
+) {
+    for (entity, mut animator, mut sprite, mut atlas, mut visibility, spot, play) in query.iter_mut() {
+        // nobody is standing at this spot
+        let npc = if let Some(npc) = game.npc_data.npc_at(spot.0) {
+            npc
+        } else {
+            visibility.is_visible = false;
+            continue;
+        };
+
+        let animations = &npc.animations;
+        visibility.is_visible = true;
+
+        if *atlas != animations.atlas {
+            *atlas = animations.atlas.clone();
+        }
+
+        if let Some(play) = play {
+            animator.play(play.0, animations);
+            commands.entity(entity).remove::<PlayAnimation>();
+        }
+
+        let state = animator.state;
+        if let Some(clip) = animations.clip(state) {
+            animator.timer.tick(time.delta());
+            if animator.timer.just_finished() && !animator.finished {
+                if animator.frame + 1 < clip.frames.len() {
+                    animator.frame += 1;
+                } else if clip.looping {
+                    animator.frame = 0;
+                } else {
+                    animator.finished = true;
+                }
+            }
+
+            sprite.index = clip.frames[animator.frame.min(clip.frames.len() - 1)];
+            sprite.color = clip.color;
+
+            // one shot clips hand back to whatever the npc was doing before
+            let resume = animator.resume;
+            if animator.finished && state != NpcAnimationState::Leave && state != resume {
+                if let Some(resume_clip) = animations.clip(resume) {
+                    animator.start(resume, resume_clip);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use bevy::prelude::*;
+use crate::game::GameManager;
+use crate::npc::animation::{NpcAnimationState, NpcAnimator};
+use crate::npc::{NpcSprite, SpotIndex};
+use crate::ui::{CraftFailedEvent, NPC_LEVEL};
+
+const MAX_PATIENCE: f32 = 100.;
+// every failure makes patience drain this much faster
+const FAILURE_DRAIN_MULTIPLIER: f32 = 0.5;
+const FAILURE_PENALTY: f32 = 8.;
+
+const METER_WIDTH: f32 = 16.;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum Mood {
+    Angry,
+    Impatient,
+    Content,
+}
+
+impl Mood {
+    pub fn idle_state(&self) -> NpcAnimationState {
+        match self {
+            Mood::Content => NpcAnimationState::Idle,
+            Mood::Impatient => NpcAnimationState::Impatient,
+            Mood::Angry => NpcAnimationState::Angry,
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Mood::Content => Color::rgb(0.45, 0.75, 0.35),
+            Mood::Impatient => Color::rgb(0.9, 0.7, 0.25),
+            Mood::Angry => Color::rgb(0.8, 0.25, 0.2),
+        }
+    }
+}
+
+pub struct Patience {
+    value: f32,
+    rate: f32,
+    failures: u32,
+    draining: bool,
+}
+
+impl Patience {
+    /// Patience that runs out after `seconds` if nothing goes wrong.
+    pub fn lasting(seconds: f32) -> Self {
+        Patience {
+            value: MAX_PATIENCE,
+            rate: MAX_PATIENCE / seconds.max(1.),
+            failures: 0,
+            draining: true,
+        }
+    }
+
+    pub fn fraction(&self) -> f32 {
+        self.value / MAX_PATIENCE
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value <= 0.
+    }
+
+    pub fn mood(&self) -> Mood {
+        let fraction = self.fraction();
+        if fraction > 0.6 {
+            Mood::Content
+        } else if fraction > 0.25 {
+            Mood::Impatient
+        } else {
+            Mood::Angry
+        }
+    }
+
+    pub fn fail(&mut self) {
+        if self.draining {
+            self.failures += 1;
+            self.value = (self.value - FAILURE_PENALTY).max(0.);
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.draining = false;
+    }
+
+    /// Drains patience, returns true on the tick that it runs out.
+    fn drain(&mut self, seconds: f32) -> bool {
+        if !self.draining || self.is_empty() {
+            return false;
+        }
+
+        let multiplier = 1. + self.failures as f32 * FAILURE_DRAIN_MULTIPLIER;
+        self.value = (self.value - self.rate * multiplier * seconds).max(0.);
+        self.is_empty()
+    }
+}
+
+/// Sent once when the customer at the given spot runs out of patience.
+pub struct PatienceRanOutEvent(pub usize);
+
+//==================================================================================================
+//                  Patience Meter
+//==================================================================================================
+
+#[derive(Component)]
+pub struct PatienceMeter;
+
+#[derive(Component)]
+pub struct PatienceFill;
+
+pub fn spawn_patience_meter(commands: &mut Commands, spot: SpotIndex) -> (Entity, Entity) {
+    let meter = commands.spawn_bundle(SpriteBundle {
+        sprite: Sprite {
+            color: Color::rgb(0.17, 0.12, 0.1),
+            custom_size: Some(Vec2::new(METER_WIDTH, 1.)),
+            ..default()
+        },
+        visibility: Visibility { is_visible: false },
+        ..default()
+    })
+        .insert(PatienceMeter)
+        .insert(spot)
+        .insert(Name::new("Patience Meter"))
+        .id();
+
+    let fill = commands.spawn_bundle(SpriteBundle {
+        sprite: Sprite {
+            custom_size: Some(Vec2::new(METER_WIDTH - 8., 1.)),
+            ..default()
+        },
+        visibility: Visibility { is_visible: false },
+        ..default()
+    })
+        .insert(PatienceFill)
+        .insert(spot)
+        .insert(Name::new("Patience Fill"))
+        .id();
+
+    (meter, fill)
+}
+
+//==================================================================================================
+//                  Systems
+//==================================================================================================
+
+pub fn drain_patience(
+    time: Res<Time>,
+    mut game: ResMut<GameManager>,
+    mut craft_failed_event: EventReader<CraftFailedEvent>,
+    mut ran_out_event: EventWriter<PatienceRanOutEvent>,
+) {
+    let failures = craft_failed_event.iter().count();
+
+    for (index, spot) in game.npc_data.spots.iter_mut().enumerate() {
+        if let Some(patience) = spot.patience.as_mut() {
+            for _ in 0..failures {
+                patience.fail();
+            }
+
+            if patience.drain(time.delta_seconds()) {
+                ran_out_event.send(PatienceRanOutEvent(index));
+            }
+        }
+    }
+}
+
+pub fn update_mood_expression(
+    game: Res<GameManager>,
+    mut query: Query<(&mut NpcAnimator, &SpotIndex), With<NpcSprite>>,
+) {
+    for (mut animator, spot) in query.iter_mut() {
+        if let Some(npc) = game.npc_data.npc_at(spot.0) {
+            let idle = game.npc_data.mood_at(spot.0).idle_state();
+            let is_idling = matches!(animator.resume(), NpcAnimationState::Idle | NpcAnimationState::Impatient | NpcAnimationState::Angry);
+            if is_idling && animator.resume() != idle {
+                animator.play(idle, &npc.animations);
+            }
+        }
+    }
+}
+
+pub fn update_patience_meters(
+    game: Res<GameManager>,
+    sprites: Query<(&Transform, &TextureAtlasSprite, &SpotIndex), (With<NpcSprite>, Without<PatienceMeter>, Without<PatienceFill>)>,
+    mut meters: Query<(&mut Transform, &mut Sprite, &mut Visibility, &SpotIndex), (With<PatienceMeter>, Without<PatienceFill>)>,
+    mut fills: Query<(&mut Transform, &mut Sprite, &mut Visibility, &SpotIndex), (With<PatienceFill>, Without<PatienceMeter>)>,
+) {
+    for (sprite_transform, sprite, spot) in sprites.iter() {
+        let size = sprite.custom_size.unwrap_or(Vec2::ONE);
+        let patience = game.npc_data.spot(spot.0)
+            .filter(|spot| spot.npc.is_some())
+            .and_then(|spot| spot.patience.as_ref());
+
+        // the meter stands to the left of the portrait, as tall as half of it
+        let height = size.y / 2.;
+        let bottom = sprite_transform.translation.y - size.y / 2.;
+        let x = sprite_transform.translation.x - size.x / 2. - METER_WIDTH;
+
+        for (mut transform, mut meter, mut visibility, meter_spot) in meters.iter_mut() {
+            if meter_spot == spot {
+                visibility.is_visible = patience.is_some();
+                meter.custom_size = Some(Vec2::new(METER_WIDTH, height));
+                transform.translation = Vec3::new(x, bottom + height / 2., NPC_LEVEL + 1.);
+            }
+        }
+
+        for (mut transform, mut fill, mut visibility, fill_spot) in fills.iter_mut() {
+            if fill_spot == spot {
+                visibility.is_visible = patience.is_some();
+                if let Some(patience) = patience {
+                    let fill_height = (height - 8.) * patience.fraction();
+                    fill.color = patience.mood().color();
+                    fill.custom_size = Some(Vec2::new(METER_WIDTH - 8., fill_height));
+                    transform.translation = Vec3::new(x, bottom + 4. + fill_height / 2., NPC_LEVEL + 2.);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use bevy::utils::HashMap;
+use crate::element::{Element, Tag};
+use crate::npc::animation::NpcAnimationState;
+
+// anything this many crafts away from the order counts as "close"
+const CLOSE_STEPS: u32 = 2;
+
+pub struct Reaction {
+    pub line: String,
+    pub emotion: Option<NpcAnimationState>,
+}
+
+impl Reaction {
+    pub fn new(line: &str, emotion: Option<NpcAnimationState>) -> Self {
+        Reaction {
+            line: line.to_string(),
+            emotion,
+        }
+    }
+
+    /// What any customer says when nothing more specific was written for the element, based on how
+    /// many crafts it is away from their order.
+    pub fn generic(steps: Option<u32>) -> Self {
+        match steps {
+            Some(1) => Reaction::new("Ooh, close! That just needs one more step.", Some(NpcAnimationState::Happy)),
+            Some(steps) if steps <= CLOSE_STEPS => Reaction::new("That is on the right track, but it is not there yet.", None),
+            Some(_) => Reaction::new("I can see where you are going with that, but it is a long way from what I ordered.", None),
+            None => Reaction::new("That is not what I ordered.", Some(NpcAnimationState::Disgusted)),
+        }
+    }
+}
+
+/// Lines a customer says when an element is handed to them that isnt their order.
+#[derive(Default)]
+pub struct Reactions {
+    wrong: Option<String>,
+    close: Option<String>,
+    loved: HashMap<Element, String>,
+    hated: HashMap<Element, String>,
+    loved_tags: Vec<(Tag, String)>,
+    hated_tags: Vec<(Tag, String)>,
+}
+
+impl Reactions {
+    pub fn with_wrong_line(mut self, line: &str) -> Self {
+        self.wrong = Some(line.to_string());
+        self
+    }
+
+    pub fn with_close_line(mut self, line: &str) -> Self {
+        self.close = Some(line.to_string());
+        self
+    }
+
+    pub fn with_loved(mut self, element: &Element, line: &str) -> Self {
+        self.loved.insert(element.clone(), line.to_string());
+        self
+    }
+
+    pub fn with_hated(mut self, element: &Element, line: &str) -> Self {
+        self.hated.insert(element.clone(), line.to_string());
+        self
+    }
+
+    pub fn with_loved_tag(mut self, tag: Tag, line: &str) -> Self {
+        self.loved_tags.push((tag, line.to_string()));
+        self
+    }
+
+    pub fn with_hated_tag(mut self, tag: Tag, line: &str) -> Self {
+        self.hated_tags.push((tag, line.to_string()));
+        self
+    }
+
+    // specific elements first, then how close it is, and only then what kind of food it is
+    pub fn react(&self, element: &Element, steps: Option<u32>) -> Option<Reaction> {
+        if let Some(line) = self.loved.get(element) {
+            return Some(Reaction::new(line, Some(NpcAnimationState::Happy)));
+        }
+
+        if let Some(line) = self.hated.get(element) {
+            return Some(Reaction::new(line, Some(NpcAnimationState::Disgusted)));
+        }
+
+        if let (Some(steps), Some(line)) = (steps, self.close.as_ref()) {
+            if steps <= CLOSE_STEPS {
+                return Some(Reaction::new(line, Some(NpcAnimationState::Happy)));
+            }
+        }
+
+        if let Some((_, line)) = self.loved_tags.iter().find(|(tag, _)| element.has_tag(*tag)) {
+            return Some(Reaction::new(line, Some(NpcAnimationState::Happy)));
+        }
+
+        if let Some((_, line)) = self.hated_tags.iter().find(|(tag, _)| element.has_tag(*tag)) {
+            return Some(Reaction::new(line, Some(NpcAnimationState::Disgusted)));
+        }
+
+        self.wrong.as_ref().map(|line| Reaction::new(line, None))
+    }
+}
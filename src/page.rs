@@ -1,15 +1,24 @@
+mod book;
+
+use std::time::Duration;
+use bevy::ecs::system::EntityCommands;
 use bevy::prelude::*;
 use bevy::text::Text2dBounds;
 use bevy_inspector_egui::RegisterInspectable;
-use bevy_kira_audio::{AudioChannel, AudioControl};
 use bevy_prototype_debug_lines::DebugLines;
 use rand::{Rng, RngCore};
-use crate::boss_fight::{Clickable, ClickableBundle, on_click};
+use crate::boss_fight::{BossTimer, Clickable, ClickableBundle, on_click};
+use crate::difficulty::Difficulty;
+use crate::focus::Focusable;
 use crate::element::Element;
 use crate::game::GameManager;
 use crate::{BossFightPlugin, GameHelper};
-use crate::audio::SfxChannel;
-use crate::ui::{StaticClickable, ElementInfoEvent, Rect, SLOT_LEVEL, TEXT_LEVEL, UI_LEVEL};
+use crate::audio::sfx::{PlaySfxEvent, Sfx};
+use crate::locale::Localization;
+use crate::npc::animation::NPC_FRAME_SIZE;
+use crate::page::book::{bestiary_entry, Book, BookPage, CHAPTERS, discovered_recipes, index_page_count, meet_npcs, RECIPES_PER_PAGE};
+use crate::registry::{Ingredient, Recipe, Registry};
+use crate::ui::{CraftType, StaticClickable, ElementInfoEvent, Rect, SLOT_LEVEL, TEXT_LEVEL, UI_LEVEL, UiData};
 
 pub struct PagePlugin;
 
@@ -22,69 +31,388 @@ impl Plugin for PagePlugin {
     fn build(&self, app: &mut App) {
         app
             //.register_inspectable::<Clickable<PageCloseEvent>>()
+            .init_resource::<Book>()
             .add_event::<PageCloseEvent>()
+            .add_event::<PageNextEvent>()
+            .add_event::<PagePrevEvent>()
             .add_startup_system(setup)
             .add_system(move_page)
             .add_system(on_click::<PageCloseEvent>)
+            .add_system(on_click::<PageNextEvent>)
+            .add_system(on_click::<PagePrevEvent>)
             .add_system(close_info)
+            .add_system(meet_npcs)
+            .add_system(turn_page)
+            .add_system(animate_page_curl)
             .add_system_to_stage(CoreStage::PostUpdate, on_click_close_page)
-            .add_system_to_stage(CoreStage::PostUpdate, listen_for_right_click);
+            .add_system_to_stage(CoreStage::PostUpdate, listen_for_right_click)
+            .add_system_to_stage(CoreStage::PostUpdate, render_book_page.after(listen_for_right_click));
     }
 }
 
 fn listen_for_right_click(
     game: Res<GameManager>,
     mut commands: Commands,
-    mut asset_server: Res<AssetServer>,
+    asset_server: Res<AssetServer>,
+    mut book: ResMut<Book>,
     mut element_info_event: EventReader<ElementInfoEvent>,
-    mut query_page: Query<&mut Transform, With<Page>>,
+    mut sfx: EventWriter<PlaySfxEvent>,
+    difficulty: Res<Difficulty>,
+    boss_timers: Query<&BossTimer>,
+) {
+    if !difficulty.allows_cookbook() && boss_timers.iter().any(|timer| timer.is_ticking()) {
+        element_info_event.clear();
+        return;
+    }
+
+    for info in element_info_event.iter() {
+        let element: &Element = &info.0;
+        book.open(BookPage::Element(element.clone()));
+
+        // make page move left
+        commands.entity(game.pages[0]).insert(MovingTo(PagePlugin::ON_SCREEN_POS));
+
+        sfx.send(PlaySfxEvent(Sfx::PageFlip));
+    }
+}
+
+//==================================================================================================
+//                  Book
+//==================================================================================================
+
+const ELEMENT_SPRITE_SIZE: Vec2 = Vec2::new(16. * 8., 16. * 8.);
+const PAGE_SIZE: Vec2 = Vec2::new(56. * 8., 76. * 8.);
+const PAGE_CURL_SECONDS: f32 = 0.25;
+
+#[derive(Default, Debug)]
+pub struct PageNextEvent;
+
+#[derive(Default, Debug)]
+pub struct PagePrevEvent;
+
+#[derive(Component)]
+struct PageContent;
+
+#[derive(Component)]
+struct PageCurl {
+    timer: Timer,
+    forward: bool,
+}
+
+fn turn_page(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game: Res<GameManager>,
+    registry: Res<Registry>,
+    ui_data: Res<UiData>,
+    mut book: ResMut<Book>,
+    mut next_event: EventReader<PageNextEvent>,
+    mut prev_event: EventReader<PagePrevEvent>,
+    mut sfx: EventWriter<PlaySfxEvent>,
+) {
+    let turns = next_event.iter().map(|_| true).chain(prev_event.iter().map(|_| false)).collect::<Vec<bool>>();
+    if turns.is_empty() {
+        return;
+    }
+
+    let pages = book.pages(&game, &ui_data, &registry);
+    for forward in turns {
+        if book.turn(&pages, forward) {
+            let curl = commands.spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    custom_size: Some(PAGE_SIZE),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0., 0., SLOT_LEVEL + 5.),
+                texture: asset_server.load("sprites/page.png"),
+                ..default()
+            })
+                .insert(PageCurl {
+                    timer: Timer::new(Duration::from_secs_f32(PAGE_CURL_SECONDS), false),
+                    forward,
+                })
+                .insert(Name::new("Page Curl"))
+                .id();
+            commands.entity(game.pages[0]).add_child(curl);
+
+            sfx.send(PlaySfxEvent(Sfx::PageFlip));
+        }
+    }
+}
+
+// the old page folds over towards the spine and darkens as it goes, uncovering the new one
+fn animate_page_curl(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut PageCurl, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, mut curl, mut transform, mut sprite) in query.iter_mut() {
+        curl.timer.tick(time.delta());
+        let progress = curl.timer.percent();
+
+        let width = PAGE_SIZE.x * (1. - progress);
+        let side = if curl.forward { -1. } else { 1. };
+        transform.translation.x = side * (PAGE_SIZE.x - width) / 2.;
+        sprite.custom_size = Some(Vec2::new(width, PAGE_SIZE.y));
+        let shade = 1. - progress * 0.5;
+        sprite.color = Color::rgb(shade, shade, shade);
+
+        if curl.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn render_book_page(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game: Res<GameManager>,
+    registry: Res<Registry>,
+    ui_data: Res<UiData>,
+    book: Res<Book>,
+    localization: Res<Localization>,
+    query_content: Query<Entity, With<PageContent>>,
     mut query_sprite: Query<(&mut Handle<Image>, &mut Sprite), With<PageItemSprite>>,
     mut query_title: Query<&mut Text, (With<PageTitle>, Without<PageText>)>,
     mut query_text: Query<&mut Text, (With<PageText>, Without<PageTitle>)>,
-    mut page_turn_audio: Res<AudioChannel<SfxChannel>>,
 ) {
-    for info in element_info_event.iter() {
-        let element: &Element = &info.0;
+    if !book.is_changed() && !localization.is_changed() {
+        return;
+    }
+
+    let page = match game.pages.first() {
+        Some(page) => *page,
+        None => return,
+    };
 
-        if let Ok(mut transform) = query_page.get_single_mut() {
-            if let Ok((mut handle, mut sprite)) = query_sprite.get_single_mut() {
-                if let Ok(mut title) = query_title.get_single_mut() {
-                    if let Ok(mut text) = query_text.get_single_mut() {
-                        let file_path = format!("sprites/{}.png", element.id);
-                        println!("{file_path}");
-                        *handle = asset_server.load(&file_path);
-                        title.sections[0].value = element.name.to_string();
-                        text.sections[0].value = element.desc.to_string();
-
-                        // make page move left
-                        commands.entity(game.pages[0]).insert(MovingTo(PagePlugin::ON_SCREEN_POS));
-
-                        // play sound
-                        let mut rand = rand::thread_rng();
-                        let r = rand.gen_range(0..3);
-                        match r {
-                            0 => {
-                                page_turn_audio.play(asset_server.load("sounds/page-flip-01.mp3")).with_volume(0.4);
-                            },
-                            1 => {
-                                page_turn_audio.play(asset_server.load("sounds/page-flip-02.mp3")).with_volume(0.4);
-                            },
-                            2 => {
-                                page_turn_audio.play(asset_server.load("sounds/page-flip-03.mp3")).with_volume(0.4);
-                            },
-                            _ => {}
-                        }
-                    }
-                }
+    let (mut handle, mut sprite) = match query_sprite.get_single_mut() {
+        Ok(sprite) => sprite,
+        Err(_) => return,
+    };
+    let (mut title, mut text) = match (query_title.get_single_mut(), query_text.get_single_mut()) {
+        (Ok(title), Ok(text)) => (title, text),
+        _ => return,
+    };
+
+    for entity in query_content.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/pixel_font.ttf"),
+        font_size: 18.,
+        color: Color::rgb(0.57, 0.42, 0.28),
+    };
+
+    let mut content = Vec::new();
+    match book.current() {
+        BookPage::Element(element) => {
+            *handle = asset_server.load(&format!("sprites/{}.png", element.id));
+            sprite.custom_size = Some(ELEMENT_SPRITE_SIZE);
+            title.sections[0].value = localization.element_name(element).to_string();
+            text.sections[0].value = match ui_data.near_miss_with(element) {
+                Some(near_miss) => format!("{}\n\n{} {}", localization.element_desc(element), localization.text("Near miss:"), near_miss.hint.text(&localization)),
+                None => localization.element_desc(element).to_string(),
+            };
+            content = spawn_recipes(&mut commands, &asset_server, &text_style, &registry, &ui_data, &localization, element);
+        }
+        BookPage::Chapter(index) => {
+            let chapter = &CHAPTERS[*index];
+            let npc = game.npc_data.npc(*index);
+            *handle = npc.map_or_else(|| asset_server.load("sprites/empty.png"), |npc| npc.sprite.clone());
+            sprite.custom_size = Some(NPC_FRAME_SIZE * 4.);
+            title.sections[0].value = localization.format("Chapter {}: {}", &[&(index + 1).to_string(), localization.text(chapter.title)]);
+            let name = npc.map_or("Someone", |npc| npc.name.as_str());
+            text.sections[0].value = localization.format("{} came in asking for...", &[localization.text(name)]);
+
+            let mut x = -96.;
+            for dish in chapter.dishes {
+                content.push(spawn_recipe_element(&mut commands, &asset_server, &text_style, &ui_data, dish, Vec2::new(x, RECIPE_TOP)));
+                x += 48.;
+            }
+        }
+        BookPage::RecipeIndex(index) => {
+            let recipes = discovered_recipes(&registry, &ui_data);
+            let page_count = index_page_count(recipes.len());
+            *handle = asset_server.load("sprites/empty.png");
+            title.sections[0].value = localization.text("Recipes").to_string();
+            text.sections[0].value = if recipes.is_empty() {
+                localization.text("Nothing discovered yet. Get cooking!").to_string()
+            } else {
+                localization.format("Page {} of {}", &[&(index + 1).to_string(), &page_count.to_string()])
+            };
+
+            let mut y = INDEX_TOP;
+            for recipe in recipes.iter().skip(index * RECIPES_PER_PAGE).take(RECIPES_PER_PAGE) {
+                content.append(&mut spawn_recipe_row(&mut commands, &asset_server, &text_style, &ui_data, &localization, recipe, y));
+                y -= RECIPE_ROW_HEIGHT;
             }
         }
+        BookPage::Bestiary(kind) => {
+            let npc = (0..game.npc_data.npc_count())
+                .filter_map(|index| game.npc_data.npc(index))
+                .find(|npc| npc.kind == *kind);
+            *handle = npc.map_or_else(|| asset_server.load("sprites/empty.png"), |npc| npc.sprite.clone());
+            sprite.custom_size = Some(NPC_FRAME_SIZE * 4.);
+            title.sections[0].value = npc.map_or_else(String::new, |npc| localization.text(&npc.name).to_string());
+            text.sections[0].value = localization.text(bestiary_entry(kind)).to_string();
+        }
+    }
+
+    commands.entity(page).push_children(&content);
+}
+
+//==================================================================================================
+//                  Recipes
+//==================================================================================================
+
+const RECIPE_TOP: f32 = -120.;
+const INDEX_TOP: f32 = -45.;
+const RECIPE_ROW_HEIGHT: f32 = 36.;
+const RECIPE_ICON_SIZE: f32 = 32.;
+// the page only has room for this many recipes under the description
+const MAX_RECIPE_ROWS: usize = 4;
+
+fn spawn_recipes(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    text_style: &TextStyle,
+    registry: &Registry,
+    ui_data: &UiData,
+    localization: &Localization,
+    element: &Element,
+) -> Vec<Entity> {
+    let made_from = registry.recipes_for(element);
+    let used_in = registry.recipes_using(element);
+
+    // anything that doesn't fit is summed up in a "+N more" line, which takes the place of a row
+    let made_rows = made_from.len().min(1);
+    let used_room = MAX_RECIPE_ROWS - made_rows - (made_from.len() > made_rows) as usize;
+    let used_rows = if used_in.len() > used_room { used_room - 1 } else { used_in.len() };
+
+    let mut y = RECIPE_TOP;
+    let mut entities = Vec::new();
+    for (header, recipes, shown) in [("Made from", &made_from, made_rows), ("Used in", &used_in, used_rows)] {
+        if recipes.is_empty() {
+            continue;
+        }
+
+        entities.push(spawn_recipe_text(commands, text_style, localization.text(header), Vec2::new(-119.7, y)));
+        y -= RECIPE_ROW_HEIGHT * 0.75;
+
+        for recipe in &recipes[..shown] {
+            entities.append(&mut spawn_recipe_row(commands, asset_server, text_style, ui_data, localization, recipe, y));
+            y -= RECIPE_ROW_HEIGHT;
+        }
+
+        if recipes.len() > shown {
+            let more = localization.format("+{} more", &[&(recipes.len() - shown).to_string()]);
+            entities.push(spawn_recipe_text(commands, text_style, &more, Vec2::new(-119.7, y)));
+            y -= RECIPE_ROW_HEIGHT * 0.75;
+        }
     }
+
+    entities
+}
+
+/// A row reads as: tool, ingredients, result. Anything not discovered yet is left as "???",
+/// pattern inputs are written out as the tag they take.
+fn spawn_recipe_row(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    text_style: &TextStyle,
+    ui_data: &UiData,
+    localization: &Localization,
+    recipe: &Recipe,
+    y: f32,
+) -> Vec<Entity> {
+    let mut entities = Vec::new();
+
+    let (tool_path, tool_size) = match recipe.tool {
+        CraftType::MIXER => ("sprites/mixer.png", Vec2::new(40., 22.)),
+        CraftType::FURNACE => ("sprites/furnace.png", Vec2::new(38., 38.)),
+        CraftType::SLICER => ("sprites/slicer.png", Vec2::new(40., 22.)),
+    };
+    let tool_size = tool_size * RECIPE_ICON_SIZE / tool_size.y.max(tool_size.x);
+    entities.push(spawn_recipe_icon(commands, asset_server.load(tool_path), tool_size, Vec2::new(-96., y)));
+
+    let mut x = -48.;
+    for (i, ingredient) in recipe.ingredients.iter().enumerate() {
+        if i > 0 {
+            entities.push(spawn_recipe_text(commands, text_style, "+", Vec2::new(x - 26., y + 10.)));
+        }
+        let pos = Vec2::new(x, y);
+        entities.push(match ingredient {
+            Ingredient::Exact(element) => spawn_recipe_element(commands, asset_server, text_style, ui_data, element, pos),
+            Ingredient::Tagged(tags) => {
+                let tags = tags.iter().map(|tag| localization.text(tag.name())).collect::<Vec<_>>().join(" ");
+                spawn_recipe_text(commands, text_style, &tags, Vec2::new(pos.x - 16., pos.y + 10.))
+            }
+            Ingredient::Any => spawn_recipe_text(commands, text_style, localization.text("Any"), Vec2::new(pos.x - 16., pos.y + 10.)),
+        });
+        x += 48.;
+    }
+
+    entities.push(spawn_recipe_text(commands, text_style, "=", Vec2::new(x - 26., y + 10.)));
+    entities.push(spawn_recipe_element(commands, asset_server, text_style, ui_data, &recipe.result, Vec2::new(x, y)));
+
+    entities
 }
 
-fn close_info(keys: Res<Input<KeyCode>>, game: Res<GameManager>, mut commands: Commands) {
+fn spawn_recipe_element(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    text_style: &TextStyle,
+    ui_data: &UiData,
+    element: &Element,
+    pos: Vec2,
+) -> Entity {
+    if ui_data.known_elements().contains(element) {
+        let texture = asset_server.load(&format!("sprites/{}.png", element.id));
+        spawn_recipe_icon(commands, texture, Vec2::splat(RECIPE_ICON_SIZE), pos)
+    } else {
+        spawn_recipe_text(commands, text_style, "???", Vec2::new(pos.x - 16., pos.y + 10.))
+    }
+}
+
+fn spawn_recipe_icon(commands: &mut Commands, texture: Handle<Image>, size: Vec2, pos: Vec2) -> Entity {
+    commands.spawn_bundle(SpriteBundle {
+        sprite: Sprite {
+            custom_size: Some(size),
+            ..default()
+        },
+        transform: Transform::from_xyz(pos.x, pos.y, SLOT_LEVEL),
+        texture,
+        ..default()
+    })
+        .insert(PageContent)
+        .insert(Name::new("Page Recipe Icon"))
+        .id()
+}
+
+fn spawn_recipe_text(commands: &mut Commands, text_style: &TextStyle, text: &str, pos: Vec2) -> Entity {
+    commands.spawn_bundle(Text2dBundle {
+        text: Text::from_section(text, text_style.clone()).with_alignment(TextAlignment {
+            vertical: VerticalAlign::Top,
+            horizontal: HorizontalAlign::Left,
+        }),
+        transform: Transform::from_xyz(pos.x, pos.y, 1.0),
+        ..default()
+    })
+        .insert(PageContent)
+        .insert(Name::new("Page Recipe Text"))
+        .id()
+}
+
+fn close_info(keys: Res<Input<KeyCode>>, game: Res<GameManager>, ui_data: Res<UiData>, mut commands: Commands) {
     if keys.just_pressed(KeyCode::Escape) {
         commands.entity(game.pages[0]).insert(MovingTo(PagePlugin::OFF_SCREEN_POS));
     }
+
+    // the book opens back up to wherever it was left, unless the player is typing a search
+    if keys.just_pressed(KeyCode::B) && !ui_data.view.search_focused {
+        commands.entity(game.pages[0]).insert(MovingTo(PagePlugin::ON_SCREEN_POS));
+    }
 }
 
 #[derive(Component)]
@@ -205,11 +533,44 @@ fn setup(mut game: ResMut<GameManager>, mut commands: Commands, asset_server: Re
                 rect: Rect::new(-50., 50., 50., -50.),
                 event: PageCloseEvent
         })
+        .insert(Focusable::sized(Vec2::splat(100.)))
         .insert(PageCloseButton)
         .insert(Name::new("Page Button"))
         .id();
 
-    commands.entity(parent).push_children(&[title, text, sprite, button]);
+    // page turning arrows
+    let prev_arrow = spawn_page_arrow(&mut commands, &asset_server, Vec2::new(-140., -272.), false)
+        .insert(Clickable {
+            rect: Rect::new(-24., 24., 24., -24.),
+            event: PagePrevEvent
+        })
+        .insert(Focusable::sized(Vec2::splat(48.)))
+        .id();
+    let next_arrow = spawn_page_arrow(&mut commands, &asset_server, Vec2::new(140., -272.), true)
+        .insert(Clickable {
+            rect: Rect::new(-24., 24., 24., -24.),
+            event: PageNextEvent
+        })
+        .insert(Focusable::sized(Vec2::splat(48.)))
+        .id();
+
+    commands.entity(parent).push_children(&[title, text, sprite, button, prev_arrow, next_arrow]);
+}
+
+fn spawn_page_arrow<'w, 's, 'a>(commands: &'a mut Commands<'w, 's>, asset_server: &Res<AssetServer>, pos: Vec2, forward: bool) -> EntityCommands<'w, 's, 'a> {
+    let angle = if forward { -90_f32 } else { 90_f32 };
+    let mut arrow = commands.spawn_bundle(SpriteBundle {
+        sprite: Sprite {
+            custom_size: Some(Vec2::splat(9. * 5.)),
+            ..default()
+        },
+        transform: Transform::from_xyz(pos.x, pos.y, 1.)
+            .with_rotation(Quat::from_rotation_z(angle.to_radians())),
+        texture: asset_server.load("sprites/page_up.png"),
+        ..default()
+    });
+    arrow.insert(Name::new(if forward { "Next Page Arrow" } else { "Previous Page Arrow" }));
+    arrow
 }
 
 #[derive(Default, Debug)]
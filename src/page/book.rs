@@ -0,0 +1,145 @@
+use bevy::prelude::*;
+use crate::element::Element;
+use crate::game::GameManager;
+use crate::npc::{MAIN_SPOT, NpcKind};
+use crate::registry::{Recipe, Registry};
+use crate::ui::UiData;
+
+pub const RECIPES_PER_PAGE: usize = 6;
+
+pub struct Chapter {
+    pub title: &'static str,
+    pub dishes: &'static [Element],
+}
+
+// one chapter per customer, in the order they walk in
+pub const CHAPTERS: [Chapter; 5] = [
+    Chapter {
+        title: "A Cold Start",
+        dishes: &[Element::GLACIER_ICE, Element::SHAVED_ICE, Element::UTTER_ICE_CREAM],
+    },
+    Chapter {
+        title: "The Knight Errand",
+        dishes: &[Element::ICE_CREAM_SANDWICH],
+    },
+    Chapter {
+        title: "The Shy Farmer",
+        dishes: &[Element::SALAD],
+    },
+    Chapter {
+        title: "The Knight Returns",
+        dishes: &[Element::CUT_SANDWICH],
+    },
+    Chapter {
+        title: "The Critic",
+        dishes: &[Element::RAMEN],
+    },
+];
+
+pub fn bestiary_entry(kind: &NpcKind) -> &'static str {
+    match kind {
+        NpcKind::Squee => "A goblin line cook with more enthusiasm than thumbs. Knows every recipe in the kitchen, and will tell you about all of them.",
+        NpcKind::Conrad => "A knight of great volume and greater appetite. Always on a quest, always in need of provisions that survive the journey.",
+        NpcKind::Pumkinhead => "A pig farmer from just outside of town. Quiet, polite, and very particular about his salad. Do not ask about the pumpkin.",
+        NpcKind::Gordon => "The most feared food critic in the realm. Has never once been satisfied, and has never once been quiet about it.",
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum BookPage {
+    Chapter(usize),
+    RecipeIndex(usize),
+    Element(Element),
+    Bestiary(NpcKind),
+}
+
+/// What the book can show and which page it was left open on.
+pub struct Book {
+    current: BookPage,
+    met: Vec<usize>,
+}
+
+impl Default for Book {
+    fn default() -> Self {
+        Book {
+            current: BookPage::RecipeIndex(0),
+            met: Vec::new(),
+        }
+    }
+}
+
+impl Book {
+    pub fn current(&self) -> &BookPage {
+        &self.current
+    }
+
+    pub fn open(&mut self, page: BookPage) {
+        self.current = page;
+    }
+
+    pub fn has_met(&self, npc: usize) -> bool {
+        self.met.contains(&npc)
+    }
+
+    /// Every page in reading order: chapters, the recipe index, elements, then the bestiary.
+    pub fn pages(&self, game: &GameManager, ui_data: &UiData, registry: &Registry) -> Vec<BookPage> {
+        let mut pages = Vec::new();
+
+        let mut chapters: Vec<usize> = self.met.iter().copied().filter(|npc| *npc < CHAPTERS.len()).collect();
+        chapters.sort_unstable();
+        pages.extend(chapters.into_iter().map(BookPage::Chapter));
+
+        let index_pages = index_page_count(discovered_recipes(registry, ui_data).len());
+        pages.extend((0..index_pages).map(BookPage::RecipeIndex));
+
+        pages.extend(ui_data.known_elements().iter().cloned().map(BookPage::Element));
+
+        let mut kinds: Vec<NpcKind> = Vec::new();
+        for npc in self.met.iter().filter_map(|npc| game.npc_data.npc(*npc)) {
+            if !kinds.contains(&npc.kind) {
+                kinds.push(npc.kind.clone());
+            }
+        }
+        pages.extend(kinds.into_iter().map(BookPage::Bestiary));
+
+        pages
+    }
+
+    /// Moves one page forward or back, returns false if there was no page to turn to.
+    pub fn turn(&mut self, pages: &[BookPage], forward: bool) -> bool {
+        let index = pages.iter().position(|page| *page == self.current).unwrap_or(0);
+        let next = if forward {
+            index + 1
+        } else if index > 0 {
+            index - 1
+        } else {
+            return false;
+        };
+
+        if let Some(page) = pages.get(next) {
+            self.current = page.clone();
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Recipes for anything the player has made so far.
+pub fn discovered_recipes(registry: &Registry, ui_data: &UiData) -> Vec<Recipe> {
+    registry.recipes().into_iter()
+        .filter(|recipe| ui_data.known_elements().contains(&recipe.result))
+        .collect()
+}
+
+pub fn index_page_count(recipes: usize) -> usize {
+    recipes.div_ceil(RECIPES_PER_PAGE).max(1)
+}
+
+pub fn meet_npcs(game: Res<GameManager>, mut book: ResMut<Book>) {
+    if let Some(npc) = game.npc_data.spot(MAIN_SPOT).and_then(|spot| spot.npc) {
+        if !book.has_met(npc) {
+            book.met.push(npc);
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use crate::element::Element;
+use crate::locale::Localization;
+
+const BASE_POINTS : i32 = 50;
+const GARNISH_POINTS : i32 = 10;
+// for serving with the whole clock still left, scaled down as time runs out
+const TIME_POINTS : f32 = 30.;
+const EXTRA_PENALTY : i32 = 5;
+const FORBIDDEN_PENALTY : i32 = 15;
+
+/// A dish that is put together on a plate rather than crafted in a tool. Required elements can
+/// repeat, each copy has to be on the plate. Garnishes are optional and earn points.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlatingRecipe {
+    pub dish : Element,
+    pub required : &'static [Element],
+    pub garnishes : &'static [Element],
+    pub forbidden : &'static [Element],
+}
+
+impl PlatingRecipe {
+    pub const RAMEN : PlatingRecipe = PlatingRecipe::new(Element::RAMEN, &[
+        Element::BONE_CHOPSTICKS,
+        Element::PORK_BROTH,
+        Element::HARD_BOILED_EGG,
+        Element::CHASHU,
+        Element::RAMEN_NOODLES,
+        Element::DRIED_SEAWEED,
+    ])
+        .with_garnishes(&[Element::PEPPER_FLAKES, Element::GLACIER_ICE])
+        .with_forbidden(&[Element::RAW_PORK, Element::MAGMA_PEPPER]);
+
+    pub const SALAD : PlatingRecipe = PlatingRecipe::new(Element::SALAD, &[
+        Element::SIREN_SEAWEED,
+        Element::SALAD_TOPPING,
+    ])
+        .with_garnishes(&[Element::DICED_CROUTONS, Element::GRIFFON_EGG])
+        .with_forbidden(&[Element::RAW_PORK, Element::MAGMA_PEPPER]);
+
+    pub const fn new(dish : Element, required : &'static [Element]) -> Self {
+        PlatingRecipe {
+            dish,
+            required,
+            garnishes : &[],
+            forbidden : &[],
+        }
+    }
+
+    pub const fn with_garnishes(mut self, garnishes : &'static [Element]) -> Self {
+        self.garnishes = garnishes;
+        self
+    }
+
+    pub const fn with_forbidden(mut self, forbidden : &'static [Element]) -> Self {
+        self.forbidden = forbidden;
+        self
+    }
+
+    pub fn evaluate(&self, plated : &[Element]) -> PlatingResult {
+        let mut left : Vec<Element> = plated.to_vec();
+        let mut missing = Vec::new();
+
+        for required in self.required {
+            match left.iter().position(|element| element == required) {
+                Some(index) => { left.remove(index); }
+                None => missing.push(required.clone()),
+            }
+        }
+
+        let mut result = PlatingResult { missing, ..Default::default() };
+        for element in left {
+            if self.forbidden.contains(&element) {
+                result.forbidden.push(element);
+            } else if self.garnishes.contains(&element) && !result.garnishes.contains(&element) {
+                result.garnishes.push(element);
+            } else {
+                result.extras.push(element);
+            }
+        }
+        result
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PlatingResult {
+    pub missing : Vec<Element>,
+    pub garnishes : Vec<Element>,
+    // anything on the plate the recipe doesn't ask for, including a second copy of a garnish
+    pub extras : Vec<Element>,
+    pub forbidden : Vec<Element>,
+}
+
+impl PlatingResult {
+    /// Whether the dish can be served at all. Wrong extras only cost points.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Grades a complete plate, `time_left` being the share of the clock still left from 0 to 1.
+    pub fn grade(&self, time_left : f32) -> PlatingScore {
+        let time_bonus = (time_left.clamp(0., 1.) * TIME_POINTS).round() as i32;
+        let score = BASE_POINTS
+            + self.garnishes.len() as i32 * GARNISH_POINTS
+            + time_bonus
+            - self.extras.len() as i32 * EXTRA_PENALTY
+            - self.forbidden.len() as i32 * FORBIDDEN_PENALTY;
+
+        PlatingScore {
+            result : self.clone(),
+            time_bonus,
+            score : score.clamp(0, 100),
+        }
+    }
+
+    /// What the player still has to fix, and any garnishes they got right.
+    pub fn report(&self, localization : &Localization) -> String {
+        let mut lines = Vec::new();
+        if !self.missing.is_empty() {
+            lines.push(format!("{} {}", localization.text("Missing:"), names(&self.missing, localization)));
+        }
+        if !self.forbidden.is_empty() {
+            lines.push(format!("{} {}", localization.text("Take off:"), names(&self.forbidden, localization)));
+        }
+        if !self.garnishes.is_empty() {
+            lines.push(format!("{} {}", localization.text("Nice touch:"), names(&self.garnishes, localization)));
+        }
+        lines.join("\n")
+    }
+}
+
+fn names(elements : &[Element], localization : &Localization) -> String {
+    elements.iter().map(|element| localization.element_name(element)).collect::<Vec<_>>().join(", ")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Grade {
+    S,
+    A,
+    B,
+    C,
+}
+
+impl Grade {
+    pub fn from_score(score : i32) -> Grade {
+        match score {
+            90.. => Grade::S,
+            75..=89 => Grade::A,
+            60..=74 => Grade::B,
+            _ => Grade::C,
+        }
+    }
+
+    pub fn letter(&self) -> &'static str {
+        match self {
+            Grade::S => "S",
+            Grade::A => "A",
+            Grade::B => "B",
+            Grade::C => "C",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PlatingScore {
+    pub result : PlatingResult,
+    pub time_bonus : i32,
+    pub score : i32,
+}
+
+impl PlatingScore {
+    pub fn grade(&self) -> Grade {
+        Grade::from_score(self.score)
+    }
+
+    /// The lines that add up to the score, for the win screen.
+    pub fn breakdown(&self, localization : &Localization) -> String {
+        let mut lines = vec![format!("{} +{BASE_POINTS}", localization.text("Served:"))];
+        if !self.result.garnishes.is_empty() {
+            lines.push(format!("{} +{}", localization.text("Garnish:"), self.result.garnishes.len() as i32 * GARNISH_POINTS));
+        }
+        lines.push(format!("{} +{}", localization.text("Time left:"), self.time_bonus));
+        if !self.result.extras.is_empty() {
+            lines.push(format!("{} -{}", localization.text("Extras:"), self.result.extras.len() as i32 * EXTRA_PENALTY));
+        }
+        if !self.result.forbidden.is_empty() {
+            lines.push(format!("{} -{}", localization.text("Wrong items:"), self.result.forbidden.len() as i32 * FORBIDDEN_PENALTY));
+        }
+        lines.push(format!("{} {}", localization.text("Total:"), self.score));
+        lines.join("\n")
+    }
+}
@@ -1,45 +1,36 @@
-use std::hash::{Hash, Hasher};
+use std::cmp::Reverse;
 use bevy::prelude::*;
 use bevy::utils::HashMap;
-use crate::element::{Element};
+use crate::element::{Element, Tag};
 use crate::furnace::{FurnaceRecipe};
 use crate::mixer::MixerRecipe;
 use crate::slicer::SlicerRecipe;
+use crate::ui::CraftType;
 
 //==================================================================================================
-//                          Mixer Recipe Identifier
+//                          Unordered Recipe Key
 //==================================================================================================
-#[derive(Eq, Debug)]
-pub struct MixerRecipeIden {
-    item_a: Element,
-    item_b: Element,
-}
 
-impl MixerRecipeIden {
-    pub fn new(item_a: Element, item_b: Element) -> Self {
-        MixerRecipeIden {
-            item_a,
-            item_b,
-        }
-    }
+/// Identifies a set of ingredients where slot order doesn't matter. The ids are kept sorted, so
+/// any two keys for the same ingredients are identical and hash the same without allocating.
+#[derive(Hash, PartialEq, Eq, Clone, Copy, Debug)]
+pub struct UnorderedKey<const N: usize> {
+    ids: [&'static str; N],
 }
 
-impl PartialEq for MixerRecipeIden {
-    fn eq(&self, other: &Self) -> bool {
-        let case_a = self.item_a == other.item_a && self.item_b == other.item_b;
-        let case_b = self.item_a == other.item_b && self.item_b == other.item_a;
-        case_a || case_b
+impl<const N: usize> UnorderedKey<N> {
+    pub fn new(elements: [&Element; N]) -> Self {
+        let mut ids = elements.map(|element| element.id);
+        ids.sort_unstable();
+        UnorderedKey { ids }
     }
 }
 
-impl Hash for MixerRecipeIden {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        let combined = format!("{}{}", self.item_a.id, self.item_b.id);
-        let mut chars: Vec<char> = combined.chars().collect();
-        chars.sort();
-        chars.hash(state);
-    }
-}
+//==================================================================================================
+//                          Mixer Recipe Identifier
+//==================================================================================================
+
+pub type MixerRecipeIden = UnorderedKey<2>;
 
 //==================================================================================================
 //                          Furnace Recipe Identifier
@@ -60,6 +51,109 @@ impl FurnaceRecipeIden {
     }
 }
 
+//==================================================================================================
+//                          Ingredient Pattern
+//==================================================================================================
+
+/// What a recipe accepts in one of its slots. A tagged slot takes anything with all of the tags.
+#[derive(Eq, PartialEq, Debug, Clone, Default, Hash)]
+pub enum Ingredient {
+    Exact(Element),
+    Tagged(&'static [Tag]),
+    #[default]
+    Any,
+}
+
+impl Ingredient {
+    pub fn matches(&self, element: &Element) -> bool {
+        match self {
+            Ingredient::Exact(exact) => exact == element,
+            Ingredient::Tagged(tags) => tags.iter().all(|tag| element.has_tag(*tag)),
+            Ingredient::Any => true,
+        }
+    }
+
+    // the more specific a pattern is, the earlier it gets a say
+    fn specificity(&self) -> u32 {
+        match self {
+            Ingredient::Exact(_) => 2,
+            Ingredient::Tagged(_) => 1,
+            Ingredient::Any => 0,
+        }
+    }
+
+    pub fn id(&self) -> String {
+        match self {
+            Ingredient::Exact(element) => element.id.to_string(),
+            Ingredient::Tagged(tags) => format!("any_{}", tags.iter().map(|tag| tag.name().to_lowercase()).collect::<Vec<_>>().join("_")),
+            Ingredient::Any => "any".to_string(),
+        }
+    }
+}
+
+//==================================================================================================
+//                          Recipe Rule
+//==================================================================================================
+
+/// A recipe with at least one pattern for an input. Exact recipes are looked up first, after that
+/// the rules are tried from most to least specific, ties going to whichever was added first.
+#[derive(Debug, Clone)]
+pub struct RecipeRule {
+    pub tool: CraftType,
+    pub inputs: Vec<Ingredient>,
+    pub result: Element,
+}
+
+impl RecipeRule {
+    pub fn new(tool: CraftType, inputs: &[Ingredient], result: Element) -> Self {
+        RecipeRule {
+            tool,
+            inputs: inputs.to_vec(),
+            result,
+        }
+    }
+
+    pub fn priority(&self) -> u32 {
+        self.inputs.iter().map(Ingredient::specificity).sum()
+    }
+
+    pub fn matches(&self, elements: &[Element]) -> bool {
+        if self.inputs.len() != elements.len() {
+            return false;
+        }
+
+        let in_order = self.inputs.iter().zip(elements.iter()).all(|(input, element)| input.matches(element));
+        // the mixer doesn't care which slot is which
+        let reversed = self.tool == CraftType::MIXER
+            && self.inputs.iter().zip(elements.iter().rev()).all(|(input, element)| input.matches(element));
+        in_order || reversed
+    }
+}
+
+//==================================================================================================
+//                          Recipe
+//==================================================================================================
+
+/// A recipe from any of the tools, flattened so the book can show it.
+#[derive(Debug, Clone)]
+pub struct Recipe {
+    pub tool: CraftType,
+    pub ingredients: Vec<Ingredient>,
+    pub result: Element,
+}
+
+impl Recipe {
+    pub fn uses(&self, element: &Element) -> bool {
+        self.ingredients.iter().any(|ingredient| ingredient.matches(element))
+    }
+
+    /// Like `uses`, but leaves out furnace fuel since it never ends up in the dish.
+    pub fn uses_as_food(&self, element: &Element) -> bool {
+        let skip = if self.tool == CraftType::FURNACE { 1 } else { 0 };
+        self.ingredients.iter().skip(skip).any(|ingredient| ingredient.matches(element))
+    }
+}
+
 //==================================================================================================
 //                          Registry
 //==================================================================================================
@@ -68,6 +162,7 @@ pub struct Registry {
     pub mixer_recipe_registry: HashMap<MixerRecipeIden, MixerRecipe>,
     pub furnace_recipe_registry: HashMap<FurnaceRecipeIden, FurnaceRecipe>,
     pub slicer_recipe_registry: HashMap<Element, SlicerRecipe>,
+    rules: Vec<RecipeRule>,
 }
 
 impl Default for Registry {
@@ -77,6 +172,7 @@ impl Default for Registry {
             mixer_recipe_registry: Default::default(),
             furnace_recipe_registry: Default::default(),
             slicer_recipe_registry: Default::default(),
+            rules: Vec::new(),
         };
         println!("1");
         setup_registry(&mut registry);
@@ -84,6 +180,136 @@ impl Default for Registry {
     }
 }
 
+impl Registry {
+    pub fn add_rule(&mut self, rule: RecipeRule) {
+        self.rules.push(rule);
+        // stable, so equally specific rules keep the order they were added in
+        self.rules.sort_by_key(|rule| Reverse(rule.priority()));
+    }
+
+    /// What `tool` makes out of `inputs`, in slot order. Furnace inputs are fuel then object.
+    pub fn craft(&self, tool: CraftType, inputs: &[Element]) -> Option<Element> {
+        let exact = match (tool, inputs) {
+            (CraftType::MIXER, [first, second]) => self.mixer_recipe_registry
+                .get(&MixerRecipeIden::new([first, second]))
+                .map(|recipe| recipe.result.clone()),
+            (CraftType::FURNACE, [fuel, object]) => self.furnace_recipe_registry
+                .get(&FurnaceRecipeIden::new(fuel.clone(), object.clone()))
+                .map(|recipe| recipe.result.clone()),
+            (CraftType::SLICER, [object]) => self.slicer_recipe_registry
+                .get(object)
+                .map(|recipe| recipe.result.clone()),
+            _ => None,
+        };
+
+        exact.or_else(|| {
+            self.rules.iter()
+                .find(|rule| rule.tool == tool && rule.matches(inputs))
+                .map(|rule| rule.result.clone())
+        })
+    }
+
+    /// Whether `element` makes anything in `tool`, with any partner and in either slot. Goes
+    /// through `craft`, so it follows exactly the same rules.
+    pub fn can_use(&self, tool: CraftType, element: &Element) -> bool {
+        match tool {
+            CraftType::SLICER => self.craft(tool, std::slice::from_ref(element)).is_some(),
+            CraftType::MIXER | CraftType::FURNACE => Element::ELEMENTS.iter().any(|other| {
+                self.craft(tool, &[element.clone(), other.clone()]).is_some()
+                    || self.craft(tool, &[other.clone(), element.clone()]).is_some()
+            }),
+        }
+    }
+
+    pub fn recipes(&self) -> Vec<Recipe> {
+        let mut recipes = Vec::new();
+
+        for recipe in self.mixer_recipe_registry.values() {
+            recipes.push(Recipe {
+                tool: CraftType::MIXER,
+                ingredients: vec![Ingredient::Exact(recipe.first.clone()), Ingredient::Exact(recipe.second.clone())],
+                result: recipe.result.clone(),
+            });
+        }
+
+        for recipe in self.furnace_recipe_registry.values() {
+            recipes.push(Recipe {
+                tool: CraftType::FURNACE,
+                ingredients: vec![recipe.fuel.clone(), recipe.object.clone()],
+                result: recipe.result.clone(),
+            });
+        }
+
+        for recipe in self.slicer_recipe_registry.values() {
+            recipes.push(Recipe {
+                tool: CraftType::SLICER,
+                ingredients: vec![Ingredient::Exact(recipe.object.clone())],
+                result: recipe.result.clone(),
+            });
+        }
+
+        for rule in self.rules.iter() {
+            recipes.push(Recipe {
+                tool: rule.tool,
+                ingredients: rule.inputs.clone(),
+                result: rule.result.clone(),
+            });
+        }
+
+        // hash maps have no order, keep the book from shuffling every time it opens
+        recipes.sort_by(|a, b| a.result.id.cmp(b.result.id));
+        recipes
+    }
+
+    /// Every recipe that makes `element`.
+    pub fn recipes_for(&self, element: &Element) -> Vec<Recipe> {
+        self.recipes().into_iter().filter(|recipe| recipe.result == *element).collect()
+    }
+
+    /// Every recipe that needs `element`, fuel included.
+    pub fn recipes_using(&self, element: &Element) -> Vec<Recipe> {
+        self.recipes().into_iter().filter(|recipe| recipe.uses(element)).collect()
+    }
+
+    /// Every element that can be crafted with `element` as one of its ingredients. Furnace fuel is
+    /// left out, it only heats or cools the dish and never ends up in it.
+    pub fn crafted_from(&self, element: &Element) -> Vec<Element> {
+        self.recipes().into_iter()
+            .filter(|recipe| recipe.uses_as_food(element))
+            .map(|recipe| recipe.result)
+            .collect()
+    }
+
+    /// How many crafts it takes to turn `element` into `goal`, or `None` if `goal` can't be made
+    /// from it at all.
+    pub fn steps_between(&self, element: &Element, goal: &Element) -> Option<u32> {
+        let mut visited = vec![element.clone()];
+        let mut frontier = vec![element.clone()];
+        let mut steps = 0;
+
+        while !frontier.is_empty() {
+            if frontier.contains(goal) {
+                return Some(steps);
+            }
+
+            let mut next = Vec::new();
+            for current in frontier.iter() {
+                for result in self.crafted_from(current) {
+                    if !visited.contains(&result) {
+                        visited.push(result.clone());
+                        next.push(result);
+                    }
+                }
+            }
+
+            frontier = next;
+            steps += 1;
+        }
+
+        None
+    }
+}
+
 pub struct RegistryPlugin;
 
 impl Plugin for RegistryPlugin {
@@ -108,9 +334,14 @@ fn setup_registry(mut _registry: &mut Registry) {
 }
 
 // FurnaceRecipe { fuel, object, result, id }
+// anything with a pattern for the fuel or object is a rule instead
 fn add_furnace_recipes_to_registry(registry: &mut Registry) {
     for fr in FurnaceRecipe::RECIPES {
-        registry.furnace_recipe_registry.insert(FurnaceRecipeIden::new(fr.fuel.clone(), fr.object.clone()), fr.clone());
+        if let (Ingredient::Exact(fuel), Ingredient::Exact(object)) = (&fr.fuel, &fr.object) {
+            registry.furnace_recipe_registry.insert(FurnaceRecipeIden::new(fuel.clone(), object.clone()), fr.clone());
+        } else {
+            registry.add_rule(RecipeRule::new(CraftType::FURNACE, &[fr.fuel.clone(), fr.object.clone()], fr.result.clone()));
+        }
     }
 }
 
@@ -127,7 +358,108 @@ fn add_slicer_recipes_to_registry(registry: &mut Registry) {
 fn add_mixer_recipes_to_registry(registry: &mut Registry) {
     for mr in MixerRecipe::RECIPES {
         println!("{:?}", mr);
-        registry.mixer_recipe_registry.insert(MixerRecipeIden::new(mr.first.clone(), mr.second.clone()), mr.clone());
+        registry.mixer_recipe_registry.insert(MixerRecipeIden::new([&mr.first, &mr.second]), mr.clone());
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    use super::*;
+
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn registry_elements(registry: &Registry) -> Vec<Element> {
+        let mut elements = Vec::new();
+        for recipe in registry.recipes() {
+            for ingredient in recipe.ingredients {
+                if let Ingredient::Exact(element) = ingredient {
+                    elements.push(element);
+                }
+            }
+            elements.push(recipe.result);
+        }
+        elements.sort_by_key(|element| element.id);
+        elements.dedup();
+        elements
+    }
+
+    fn same_pair(a: (&Element, &Element), b: (&Element, &Element)) -> bool {
+        (a.0 == b.0 && a.1 == b.1) || (a.0 == b.1 && a.1 == b.0)
+    }
+
+    #[test]
+    fn mixer_key_ignores_order() {
+        let registry = Registry::default();
+        let elements = registry_elements(&registry);
+
+        for a in elements.iter() {
+            for b in elements.iter() {
+                let forward = MixerRecipeIden::new([a, b]);
+                let backward = MixerRecipeIden::new([b, a]);
+                assert_eq!(forward, backward, "{} + {}", a.id, b.id);
+                assert_eq!(hash_of(&forward), hash_of(&backward), "{} + {}", a.id, b.id);
+            }
+        }
+    }
+
+    #[test]
+    fn mixer_key_hash_agrees_with_eq() {
+        let registry = Registry::default();
+        let elements = registry_elements(&registry);
+
+        let mut pairs = Vec::new();
+        for (i, a) in elements.iter().enumerate() {
+            for b in elements.iter().skip(i) {
+                pairs.push((a, b));
+            }
+        }
+
+        for first in pairs.iter() {
+            let first_key = MixerRecipeIden::new([first.0, first.1]);
+            for second in pairs.iter() {
+                let second_key = MixerRecipeIden::new([second.0, second.1]);
+                assert_eq!(first_key == second_key, same_pair(*first, *second), "{:?} vs {:?}", first_key, second_key);
+                if first_key == second_key {
+                    assert_eq!(hash_of(&first_key), hash_of(&second_key));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn mixer_recipes_craft_in_either_order() {
+        let registry = Registry::default();
+
+        for recipe in registry.mixer_recipe_registry.values() {
+            let forward = registry.craft(CraftType::MIXER, &[recipe.first.clone(), recipe.second.clone()]);
+            let backward = registry.craft(CraftType::MIXER, &[recipe.second.clone(), recipe.first.clone()]);
+            assert_eq!(forward.as_ref(), Some(&recipe.result));
+            assert_eq!(backward.as_ref(), Some(&recipe.result));
+        }
+    }
+
+    #[test]
+    fn recipes_using_can_all_be_crafted() {
+        let registry = Registry::default();
+
+        for element in Element::ELEMENTS.iter() {
+            for recipe in registry.recipes_using(element) {
+                let inputs: Vec<Vec<Element>> = match recipe.ingredients.len() {
+                    1 => vec![vec![element.clone()]],
+                    _ => Element::ELEMENTS.iter()
+                        .flat_map(|other| [vec![element.clone(), other.clone()], vec![other.clone(), element.clone()]])
+                        .collect(),
+                };
+                let craftable = inputs.iter().any(|inputs| registry.craft(recipe.tool, inputs).as_ref() == Some(&recipe.result));
+                assert!(craftable, "{} is listed as used in {}", element.id, recipe.result.id);
+            }
+        }
+    }
+}
@@ -1,7 +1,13 @@
+pub mod drag;
+pub mod hint;
+pub mod inventory;
+pub mod output;
+
 use std::alloc::dealloc;
 use std::ops::Add;
 use std::time::Duration;
 use bevy::ecs::schedule::ShouldRun::No;
+use bevy::ecs::system::SystemParam;
 use bevy::input::ButtonState;
 use bevy::input::keyboard::KeyboardInput;
 use bevy::input::mouse::MouseButtonInput;
@@ -13,11 +19,19 @@ use bevy::render::render_resource::Texture;
 use bevy::text::Text2dSize;
 use bevy::utils::tracing::event;
 use bevy_prototype_debug_lines::DebugLines;
+use crate::accessibility::AccessibilitySettings;
+use crate::audio::sfx::{PlaySfxEvent, Sfx};
 use crate::element::Element;
 use crate::{GameHelper, MixerRecipeIden};
+use crate::focus::Focusable;
 use crate::game::GameManager;
 use crate::helper::add_scaled_pixel_asset;
+use crate::locale::Localization;
 use crate::registry::{FurnaceRecipeIden, Registry};
+use crate::ui::drag::{DragOrigin, DragState, highlight_drop_targets, on_quick_send, on_swap_tool_slot, QuickSendEvent, snap_back_drops, SwapToolSlotEvent, ToolInput};
+use crate::ui::hint::{fade_craft_hints, NearMiss, show_craft_hints};
+use crate::ui::output::{animate_returning_elements, on_output_element, on_return_element, OutputElementEvent, OutputSlot, ReturnElementEvent, spawn_output_slot};
+use crate::ui::inventory::{click_view_labels, InventoryView, render_view_labels, spawn_view_labels, type_search};
 
 const TAVERN_LEVEL: f32 = 10.0;
 pub const NPC_LEVEL: f32 = 15.;
@@ -27,12 +41,16 @@ pub const TEXT_LEVEL: f32 = 40.0;
 const TOP_LEVEL: f32 = 50.0;
 const DRAG_LEVEL: f32 = 100.;
 
+// remembering every failed craft would bury the useful ones
+const MAX_NEAR_MISSES: usize = 20;
+
 pub struct UiPlugin;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app
             .init_resource::<UiData>()
+            .init_resource::<DragState>()
             .add_event::<DropElementEvent>()
             .add_event::<UpdateSlotEvent>()
             .add_event::<SlotEnteredEvent>()
@@ -46,6 +64,11 @@ impl Plugin for UiPlugin {
             .add_event::<LoadMixerEvent>()
             .add_event::<LoadSlicerEvent>()
             .add_event::<InsertElementEvent>()
+            .add_event::<ElementRewardEvent>()
+            .add_event::<OutputElementEvent>()
+            .add_event::<ReturnElementEvent>()
+            .add_event::<QuickSendEvent>()
+            .add_event::<SwapToolSlotEvent>()
             .add_event::<PageUpEvent>()
             .add_event::<PageDownEvent>()
             .add_startup_system(setup_ui)
@@ -57,12 +80,24 @@ impl Plugin for UiPlugin {
             .add_system(check_for_slicer_craft)
             .add_system(detect_click_page_arrows)
             .add_system(blinking_sprites)
+            .add_system(fade_craft_hints)
+            .add_system(animate_returning_elements)
+            .add_system(highlight_drop_targets)
+            .add_system(type_search)
+            .add_system(click_view_labels)
+            .add_system(render_view_labels)
             //.add_system(test_system)
             //.add_system(on_drop_element.after(drag_item))
             .add_system_to_stage(CoreStage::PostUpdate, on_load_furnace)
             .add_system_to_stage(CoreStage::PostUpdate, on_load_mixer)
             .add_system_to_stage(CoreStage::PostUpdate, on_load_slicer)
             .add_system_to_stage(CoreStage::PostUpdate, on_failed_craft)
+            .add_system_to_stage(CoreStage::PostUpdate, show_craft_hints)
+            .add_system_to_stage(CoreStage::PostUpdate, on_output_element)
+            .add_system_to_stage(CoreStage::PostUpdate, on_return_element)
+            .add_system_to_stage(CoreStage::PostUpdate, snap_back_drops)
+            .add_system_to_stage(CoreStage::PostUpdate, on_quick_send)
+            .add_system_to_stage(CoreStage::PostUpdate, on_swap_tool_slot)
             .add_system_to_stage(CoreStage::PostUpdate, on_insert_element)
             .add_system_to_stage(CoreStage::PostUpdate, handle_slot_events)
             .add_system_to_stage(CoreStage::PostUpdate, hide_name)
@@ -92,10 +127,10 @@ pub struct SlotLeftEvent(u32);
 pub struct RefreshSlotsEvent;
 
 #[derive(Debug)]
-pub struct ElementCraftedEvent(pub Element);
+pub struct ElementCraftedEvent(pub Element, pub CraftType);
 
 #[derive(Debug)]
-pub struct CraftFailedEvent(CraftType);
+pub struct CraftFailedEvent(pub CraftType, pub Vec<Element>);
 
 #[derive(Debug)]
 pub struct CraftRepeatedEvent(CraftType);
@@ -115,35 +150,49 @@ pub struct LoadFurnaceEvent;
 #[derive(Debug)]
 pub struct InsertElementEvent(pub Element);
 
+/// Elements handed over by a customer, as opposed to ones coming back from a tool or a drag.
+#[derive(Debug)]
+pub struct ElementRewardEvent(pub Vec<Element>);
+
 #[derive(Debug)]
 pub struct PageUpEvent;
 
 #[derive(Debug)]
 pub struct PageDownEvent;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum CraftType {
     SLICER,
     MIXER,
     FURNACE,
 }
 
+impl CraftType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CraftType::SLICER => "Slicer",
+            CraftType::MIXER => "Mixer",
+            CraftType::FURNACE => "Furnace",
+        }
+    }
+}
+
 pub fn handle_slot_events(
-    mut slot_query: Query<(&mut Slot, &GlobalTransform, &Sprite)>,
+    mut slot_query: Query<(&mut Slot, &GlobalTransform, &Sprite, Option<&OutputSlot>)>,
     mut element_drop_event: EventReader<DropElementEvent>,
     mut update_slot_event: EventReader<UpdateSlotEvent>,
 ) {
     for event in element_drop_event.iter() {
-        for (mut slot, transform, sprite) in slot_query.iter_mut() {
+        for (mut slot, transform, sprite, output) in slot_query.iter_mut() {
             let rect = Slot::generate_rect(transform, sprite);
-            if rect.is_within(event.0) && slot.can_change {
+            if rect.is_within(event.0) && slot.can_change && output.is_none() {
                 slot.element = Some(event.1.clone())
             }
         }
     }
 
     for event in update_slot_event.iter() {
-        for (mut slot, _, _) in slot_query.iter_mut() {
+        for (mut slot, _, _, _) in slot_query.iter_mut() {
             if slot.index == event.0 {
                 slot.element = event.1.clone();
             }
@@ -231,6 +280,10 @@ pub struct UiData {
     pub amount_of_slots_indices: u32,
     number_of_pages: u32,
     pub current_page: u32,
+    pub view: InventoryView,
+    near_misses: Vec<NearMiss>,
+    // crafted elements wait in the tool's output slot instead of going straight into the book
+    pub output_to_slot: bool,
 }
 
 impl UiData {
@@ -245,15 +298,29 @@ impl UiData {
     pub fn add_element(&mut self, element: Element) {
         if !self.known_elements.contains(&element) {
             self.known_elements.push(element);
-            let element_amount = self.known_elements.len();
-            self.number_of_pages = ((element_amount - (element_amount % 12)) / 12) as u32
+            self.set_visible_count(self.known_elements.len());
         }
     }
 
+    /// Whether the element is in the book or still waiting to be picked up from an output slot.
+    pub fn has_element<'a>(&self, element: &Element, outputs: impl IntoIterator<Item = &'a Slot>) -> bool {
+        self.known_elements.contains(element)
+            || outputs.into_iter().any(|slot| slot.element.as_ref() == Some(element))
+    }
+
     pub fn unsafe_add(&mut self, element: Element) {
         self.known_elements.push(element);
-        let element_amount = self.known_elements.len() as u32;
-        self.number_of_pages = ((element_amount - (element_amount % 12)) / 12) as u32
+        self.set_visible_count(self.known_elements.len());
+    }
+
+    /// The known elements after the search, filter and sort of the book are applied.
+    pub fn visible_elements(&self, registry: &Registry, localization: &Localization) -> Vec<Element> {
+        self.view.apply(&self.known_elements, registry, localization)
+    }
+
+    fn set_visible_count(&mut self, count: usize) {
+        self.number_of_pages = (count.max(1) as u32 - 1) / 12;
+        self.current_page = self.current_page.min(self.number_of_pages);
     }
 
     pub fn number_of_pages(&self) -> u32 {
@@ -263,6 +330,19 @@ impl UiData {
     pub fn known_elements(&self) -> &Vec<Element> {
         &self.known_elements
     }
+
+    pub fn record_near_miss(&mut self, near_miss: NearMiss) {
+        self.near_misses.retain(|other| other.tool != near_miss.tool || other.elements != near_miss.elements);
+        self.near_misses.push(near_miss);
+        if self.near_misses.len() > MAX_NEAR_MISSES {
+            self.near_misses.remove(0);
+        }
+    }
+
+    /// The latest near miss that `element` was part of.
+    pub fn near_miss_with(&self, element: &Element) -> Option<&NearMiss> {
+        self.near_misses.iter().rev().find(|near_miss| near_miss.involves(element))
+    }
 }
 
 impl Default for UiData {
@@ -276,6 +356,9 @@ impl Default for UiData {
             amount_of_slots_indices: 0,
             number_of_pages: 0,
             current_page: 0,
+            view: InventoryView::default(),
+            near_misses: Vec::new(),
+            output_to_slot: false,
         }
     }
 }
@@ -297,6 +380,15 @@ impl Rect {
         Self { x1, y1, x2, y2 }
     }
 
+    pub fn from_center(center: Vec2, size: Vec2) -> Self {
+        Self::new(
+            center.x - size.x / 2.0,
+            center.y + size.y / 2.0,
+            center.x + size.x / 2.0,
+            center.y - size.y / 2.0,
+        )
+    }
+
     pub fn is_within(&self, point: Vec2) -> bool {
         self.x1 <= point.x && self.x2 >= point.x && self.y1 >= point.y && self.y2 <= point.y
     }
@@ -484,8 +576,21 @@ fn test_system(
 fn blinking_sprites(
     mut blinking_sprites_q: Query<(&mut Blinking, &mut Visibility)>,
     time: Res<Time>,
+    accessibility: Res<AccessibilitySettings>,
 ) {
     for (mut blinking, mut visibility) in blinking_sprites_q.iter_mut() {
+        // held steady for as long as the blinks would have taken
+        if accessibility.reduced_motion && blinking.blinks > 0 {
+            visibility.is_visible = true;
+            blinking.timer.tick(time.delta() / 2);
+            if blinking.timer.finished() {
+                blinking.blinks -= 1;
+                visibility.is_visible = blinking.blinks > 0;
+                blinking.timer.reset()
+            }
+            continue;
+        }
+
         if blinking.blinks > 0 {
             blinking.timer.tick(time.delta());
             if visibility.is_visible {
@@ -517,10 +622,11 @@ fn detect_click_page_arrows(
 
     let up_button_rect = Slot::generate_rect(up_button_transform, up_button_sprite);
     let down_button_rect = Slot::generate_rect(down_button_transform, down_button_sprite);
+    let clicked = mouse.just_pressed(MouseButton::Left) && !game_helper.is_menu_open();
 
     if ui_data.can_move_up() {
         page_up_visibility.is_visible = true;
-        if mouse.just_pressed(MouseButton::Left) && up_button_rect.is_within(game_helper.mouse_world_pos()) {
+        if clicked && up_button_rect.is_within(game_helper.mouse_world_pos()) {
             ui_data.current_page -= 1;
             refresh_slots_event.send(RefreshSlotsEvent);
         }
@@ -530,7 +636,7 @@ fn detect_click_page_arrows(
 
     if ui_data.can_move_down() {
         page_down_visibility.is_visible = true;
-        if mouse.just_pressed(MouseButton::Left) && down_button_rect.is_within(game_helper.mouse_world_pos()) {
+        if clicked && down_button_rect.is_within(game_helper.mouse_world_pos()) {
             ui_data.current_page += 1;
             refresh_slots_event.send(RefreshSlotsEvent);
         }
@@ -539,46 +645,67 @@ fn detect_click_page_arrows(
     }
 }
 
+/// Hands a craft's result to the player, straight into the book or into the tool's output slot.
+fn deliver_result(
+    ui_data: &mut UiData,
+    tool: CraftType,
+    element: Element,
+    refresh_slots: &mut EventWriter<RefreshSlotsEvent>,
+    output_element_event: &mut EventWriter<OutputElementEvent>,
+) {
+    if ui_data.output_to_slot {
+        output_element_event.send(OutputElementEvent(tool, element));
+    } else {
+        ui_data.add_element(element);
+        refresh_slots.send(RefreshSlotsEvent);
+    }
+}
+
 fn check_for_mixer_craft(
-    mut slot_1_q: Query<&mut Slot, (With<MixerSlot1>, Without<MixerSlot2>)>,
-    mut slot_2_q: Query<&mut Slot, (With<MixerSlot2>, Without<MixerSlot1>)>,
+    mut slot_1_q: Query<(&mut Slot, &GlobalTransform), (With<MixerSlot1>, Without<MixerSlot2>)>,
+    mut slot_2_q: Query<(&mut Slot, &GlobalTransform), (With<MixerSlot2>, Without<MixerSlot1>)>,
+    outputs: Query<&Slot, (With<OutputSlot>, Without<MixerSlot1>, Without<MixerSlot2>)>,
     registy: Res<Registry>,
     mut ui_data: ResMut<UiData>,
     mut refresh_slots: EventWriter<RefreshSlotsEvent>,
     mut element_crafted_event: EventWriter<ElementCraftedEvent>,
     mut craft_failed_event: EventWriter<CraftFailedEvent>,
     mut craft_repeated_event: EventWriter<CraftRepeatedEvent>,
+    mut output_element_event: EventWriter<OutputElementEvent>,
+    mut return_element_event: EventWriter<ReturnElementEvent>,
 ) {
     let mut slot_1 = slot_1_q.get_single_mut();
     let mut slot_2 = slot_2_q.get_single_mut();
 
-    if let Ok(mut slot_1) = slot_1 {
-        if let Ok(mut slot_2) = slot_2 {
+    if let Ok((mut slot_1, transform_1)) = slot_1 {
+        if let Ok((mut slot_2, transform_2)) = slot_2 {
             if slot_1.element.is_some() && slot_2.element.is_some() {
                 let element_1 = slot_1.element.as_ref().unwrap().clone();
                 let element_2 = slot_2.element.as_ref().unwrap().clone();
 
-                let iden = MixerRecipeIden::new(element_1, element_2);
+                let iden = MixerRecipeIden::new([&element_1, &element_2]);
 
-                if iden == MixerRecipeIden::new(Element::BOILING_WATER, Element::RAW_PORK) {
+                if iden == MixerRecipeIden::new([&Element::BOILING_WATER, &Element::RAW_PORK]) {
                     ui_data.add_element(Element::COOKED_PORK);
                     ui_data.add_element(Element::BONE);
-                    ui_data.add_element(Element::PORK_BROTH);
-                    element_crafted_event.send(ElementCraftedEvent(Element::PORK_BROTH));
+                    element_crafted_event.send(ElementCraftedEvent(Element::PORK_BROTH, CraftType::MIXER));
+                    deliver_result(&mut ui_data, CraftType::MIXER, Element::PORK_BROTH, &mut refresh_slots, &mut output_element_event);
                     refresh_slots.send(RefreshSlotsEvent);
                 } else {
-                    let recipe = registy.mixer_recipe_registry.get(&iden);
-                    if recipe.is_some() {
-                        let element = recipe.as_ref().unwrap().result.clone();
-                        if !ui_data.known_elements.contains(&element) {
-                            element_crafted_event.send(ElementCraftedEvent(element.clone()));
-                            ui_data.add_element(element);
-                            refresh_slots.send(RefreshSlotsEvent);
+                    let elements = [element_1, element_2];
+                    let recipe = registy.craft(CraftType::MIXER, &elements);
+                    if let Some(element) = recipe {
+                        if !ui_data.has_element(&element, &outputs) {
+                            element_crafted_event.send(ElementCraftedEvent(element.clone(), CraftType::MIXER));
                         } else {
                             craft_repeated_event.send(CraftRepeatedEvent(CraftType::MIXER))
                         }
+                        deliver_result(&mut ui_data, CraftType::MIXER, element, &mut refresh_slots, &mut output_element_event);
                     } else {
-                        craft_failed_event.send(CraftFailedEvent(CraftType::MIXER))
+                        let [element_1, element_2] = elements.clone();
+                        return_element_event.send(ReturnElementEvent(element_1, transform_1.translation()));
+                        return_element_event.send(ReturnElementEvent(element_2, transform_2.translation()));
+                        craft_failed_event.send(CraftFailedEvent(CraftType::MIXER, elements.to_vec()))
                     }
                 }
 
@@ -590,37 +717,39 @@ fn check_for_mixer_craft(
 }
 
 fn check_for_furnace_craft(
-    mut slot_1_q: Query<&mut Slot, (With<FurnaceSlot1>, Without<FurnaceSlot2>)>,
-    mut slot_2_q: Query<&mut Slot, (With<FurnaceSlot2>, Without<FurnaceSlot1>)>,
+    mut slot_1_q: Query<(&mut Slot, &GlobalTransform), (With<FurnaceSlot1>, Without<FurnaceSlot2>)>,
+    mut slot_2_q: Query<(&mut Slot, &GlobalTransform), (With<FurnaceSlot2>, Without<FurnaceSlot1>)>,
+    outputs: Query<&Slot, (With<OutputSlot>, Without<FurnaceSlot1>, Without<FurnaceSlot2>)>,
     registy: Res<Registry>,
     mut ui_data: ResMut<UiData>,
     mut refresh_slots: EventWriter<RefreshSlotsEvent>,
     mut element_crafted_event: EventWriter<ElementCraftedEvent>,
     mut craft_failed_event: EventWriter<CraftFailedEvent>,
     mut craft_repeated_event: EventWriter<CraftRepeatedEvent>,
+    mut output_element_event: EventWriter<OutputElementEvent>,
+    mut return_element_event: EventWriter<ReturnElementEvent>,
 ) {
-    if let Ok(mut slot_1) = slot_1_q.get_single_mut() {
-        if let Ok(mut slot_2) = slot_2_q.get_single_mut() {
+    if let Ok((mut slot_1, transform_1)) = slot_1_q.get_single_mut() {
+        if let Ok((mut slot_2, transform_2)) = slot_2_q.get_single_mut() {
             if slot_1.element.is_some() && slot_2.element.is_some() {
                 let element_1 = slot_1.element.as_ref().unwrap().clone();
                 let element_2 = slot_2.element.as_ref().unwrap().clone();
 
-                let iden = FurnaceRecipeIden::new(element_1, element_2);
-
-                let recipe = registy.furnace_recipe_registry.get(&iden);
-                if recipe.is_some() {
-                    let element = recipe.as_ref().unwrap().result.clone();
-                    if !ui_data.known_elements.contains(&element) {
-                        element_crafted_event.send(ElementCraftedEvent(element.clone()));
-                        ui_data.add_element(element);
-                        refresh_slots.send(RefreshSlotsEvent);
+                let elements = [element_1, element_2];
+                let recipe = registy.craft(CraftType::FURNACE, &elements);
+                if let Some(element) = recipe {
+                    if !ui_data.has_element(&element, &outputs) {
+                        element_crafted_event.send(ElementCraftedEvent(element.clone(), CraftType::FURNACE));
                     } else {
                         //Add the you already have this response
                         craft_repeated_event.send(CraftRepeatedEvent(CraftType::FURNACE))
                     }
+                    deliver_result(&mut ui_data, CraftType::FURNACE, element, &mut refresh_slots, &mut output_element_event);
                 } else {
-                    //Add error responce
-                    craft_failed_event.send(CraftFailedEvent(CraftType::FURNACE))
+                    let [element_1, element_2] = elements.clone();
+                    return_element_event.send(ReturnElementEvent(element_1, transform_1.translation()));
+                    return_element_event.send(ReturnElementEvent(element_2, transform_2.translation()));
+                    craft_failed_event.send(CraftFailedEvent(CraftType::FURNACE, elements.to_vec()))
                 }
 
                 slot_1.element = None;
@@ -631,33 +760,34 @@ fn check_for_furnace_craft(
 }
 
 fn check_for_slicer_craft(
-    mut slot_q: Query<&mut Slot, With<SlicerSlot>>,
+    mut slot_q: Query<(&mut Slot, &GlobalTransform), With<SlicerSlot>>,
+    outputs: Query<&Slot, (With<OutputSlot>, Without<SlicerSlot>)>,
     registry: Res<Registry>,
     mut ui_data: ResMut<UiData>,
     mut refresh_slots: EventWriter<RefreshSlotsEvent>,
     mut element_crafted_event: EventWriter<ElementCraftedEvent>,
     mut craft_failed_event: EventWriter<CraftFailedEvent>,
     mut craft_repeated_event: EventWriter<CraftRepeatedEvent>,
+    mut output_element_event: EventWriter<OutputElementEvent>,
+    mut return_element_event: EventWriter<ReturnElementEvent>,
 ) {
     let mut slot = slot_q.get_single_mut();
 
-    if let Ok(mut slot) = slot {
+    if let Ok((mut slot, transform)) = slot {
         if slot.element.is_some() {
             let element = slot.element.as_ref().unwrap().clone();
-            let recipe = registry.slicer_recipe_registry.get(&element);
-            if let Some(recipe) = recipe {
-                let result = recipe.result.clone();
-                if !ui_data.known_elements.contains(&result) {
-                    element_crafted_event.send(ElementCraftedEvent(result.clone()));
-                    ui_data.add_element(result);
-                    refresh_slots.send(RefreshSlotsEvent)
+            let recipe = registry.craft(CraftType::SLICER, std::slice::from_ref(&element));
+            if let Some(result) = recipe {
+                if !ui_data.has_element(&result, &outputs) {
+                    element_crafted_event.send(ElementCraftedEvent(result.clone(), CraftType::SLICER));
                 } else {
                     // Add "already have that" response
                     craft_repeated_event.send(CraftRepeatedEvent(CraftType::SLICER))
                 }
+                deliver_result(&mut ui_data, CraftType::SLICER, result, &mut refresh_slots, &mut output_element_event);
             } else {
-                //Add not a recipe response
-                craft_failed_event.send(CraftFailedEvent(CraftType::SLICER))
+                return_element_event.send(ReturnElementEvent(element.clone(), transform.translation()));
+                craft_failed_event.send(CraftFailedEvent(CraftType::SLICER, vec![element]))
             }
 
             slot.element = None;
@@ -702,63 +832,113 @@ fn render_dragging(
     }
 }
 
+#[derive(SystemParam)]
+pub struct DragEventWriters<'w, 's> {
+    drop_element: EventWriter<'w, 's, DropElementEvent>,
+    entered_slot: EventWriter<'w, 's, SlotEnteredEvent>,
+    left_slot: EventWriter<'w, 's, SlotLeftEvent>,
+    element_info: EventWriter<'w, 's, ElementInfoEvent>,
+    insert_element: EventWriter<'w, 's, InsertElementEvent>,
+    quick_send: EventWriter<'w, 's, QuickSendEvent>,
+    swap_tool_slot: EventWriter<'w, 's, SwapToolSlotEvent>,
+    sfx: EventWriter<'w, 's, PlaySfxEvent>,
+}
+
 fn drag_item(
-    mut slot_query: Query<(&mut Slot, &GlobalTransform, &Sprite)>,
+    mut slot_query: Query<(&mut Slot, &GlobalTransform, &Sprite, Option<&OutputSlot>, Option<&ToolInput>)>,
     buttons: Res<Input<MouseButton>>,
+    keys: Res<Input<KeyCode>>,
+    time: Res<Time>,
     mut lines: ResMut<DebugLines>,
     game_helper: Res<GameHelper>,
     mut drag_info: ResMut<UiData>,
+    mut drag_state: ResMut<DragState>,
     game: Res<GameManager>,
-    mut drop_element_event: EventWriter<DropElementEvent>,
-    mut entered_slot_event: EventWriter<SlotEnteredEvent>,
-    mut left_slot_event: EventWriter<SlotLeftEvent>,
-    mut element_info_event: EventWriter<ElementInfoEvent>,
+    mut writers: DragEventWriters,
 ) {
     let mut is_in_slots = false;
+    let shift = keys.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+    let menu_open = game_helper.is_menu_open();
 
-    for (mut slot, transform, sprite) in slot_query.iter_mut() {
+    for (mut slot, transform, sprite, output, tool_input) in slot_query.iter_mut() {
         let rect = Slot::generate_rect(transform, sprite);
         //rect.draw_rect(&mut lines, Color::RED);
 
-        let is_within = rect.is_within(game_helper.mouse_world_pos());
+        let is_within = !menu_open && rect.is_within(game_helper.mouse_world_pos());
 
         if is_within {
             is_in_slots = true;
         }
 
-        if game.can_use_ui {
+        if game.can_use_ui && !menu_open {
             if is_within && buttons.just_pressed(MouseButton::Right) && slot.element.is_some() && slot.can_change {
                 slot.element = None;
             }
 
             if is_within && buttons.just_pressed(MouseButton::Left) && drag_info.currently_dragging.is_none() && slot.element.is_some() {
-                drag_info.currently_dragging = Some(slot.element.as_ref().unwrap().clone());
-                drag_info.should_change_sprite = true;
+                let element = slot.element.as_ref().unwrap().clone();
+                let double_click = drag_state.is_double_click(slot.index, time.seconds_since_startup());
 
-                if slot.can_change {
-                    slot.element = None
+                if shift && !slot.can_change {
+                    writers.quick_send.send(QuickSendEvent(element));
+                } else if double_click && tool_input.is_some() {
+                    writers.swap_tool_slot.send(SwapToolSlotEvent(slot.index));
+                } else {
+                    drag_info.currently_dragging = Some(element.clone());
+                    drag_info.should_change_sprite = true;
+                    writers.sfx.send(PlaySfxEvent(Sfx::PickUp));
+                    drag_state.origin = Some(DragOrigin {
+                        pos: transform.translation(),
+                        slot: if slot.can_change { Some(slot.index) } else { None },
+                    });
+
+                    // taking a result out of a tool is what puts it in the book
+                    if output.is_some() {
+                        writers.insert_element.send(InsertElementEvent(element));
+                    }
+
+                    if slot.can_change {
+                        slot.element = None
+                    }
                 }
             }
 
             if buttons.just_released(MouseButton::Left) && drag_info.currently_dragging.is_some() {
-                drop_element_event.send(DropElementEvent(game_helper.mouse_world_pos(), drag_info.currently_dragging.as_ref().unwrap().clone()));
+                writers.drop_element.send(DropElementEvent(game_helper.mouse_world_pos(), drag_info.currently_dragging.as_ref().unwrap().clone()));
                 drag_info.currently_dragging = None;
+                writers.sfx.send(PlaySfxEvent(Sfx::Drop));
+            }
+
+            // right dragging an element out of the book sends it to the next free tool slot
+            if let Some(element) = slot.element.as_ref().filter(|_| is_within && buttons.just_pressed(MouseButton::Right) && !slot.can_change) {
+                drag_state.right_drag = Some((slot.index, element.clone()));
+            }
+
+            let right_dragged_out = drag_state.right_drag.as_ref().is_some_and(|(index, _)| *index == slot.index && !is_within);
+            if right_dragged_out && buttons.pressed(MouseButton::Right) {
+                if let Some((_, element)) = drag_state.right_drag.take() {
+                    writers.quick_send.send(QuickSendEvent(element));
+                }
             }
         }
 
         if is_within && drag_info.last_slot_hovered != slot.index {
-            left_slot_event.send(SlotLeftEvent(drag_info.last_slot_hovered));
-            entered_slot_event.send(SlotEnteredEvent(slot.index));
+            writers.left_slot.send(SlotLeftEvent(drag_info.last_slot_hovered));
+            writers.entered_slot.send(SlotEnteredEvent(slot.index));
             drag_info.last_slot_hovered = slot.index
         }
 
         if is_within && buttons.just_pressed(MouseButton::Right) && slot.element.is_some() && !slot.can_change {
-            element_info_event.send(ElementInfoEvent(slot.element.as_ref().unwrap().clone()));
+            writers.element_info.send(ElementInfoEvent(slot.element.as_ref().unwrap().clone()));
         }
     }
 
+    if buttons.just_released(MouseButton::Right) {
+        drag_state.right_drag = None;
+    }
+
     if !is_in_slots && drag_info.last_slot_hovered != u32::MAX {
-        left_slot_event.send(SlotLeftEvent(drag_info.last_slot_hovered));
+        writers.left_slot.send(SlotLeftEvent(drag_info.last_slot_hovered));
         drag_info.last_slot_hovered = u32::MAX;
     }
 }
@@ -781,6 +961,7 @@ fn show_name(
     mut slot_query: Query<&Slot>,
     mut title: Query<(&mut Text, &mut Visibility), With<TitleText>>,
     mut slot_entered_event: EventReader<SlotEnteredEvent>,
+    localization: Res<Localization>,
 ) {
     let (mut text, mut visibility) = title.single_mut();
 
@@ -789,7 +970,7 @@ fn show_name(
             if slot.index == event.0 && slot.element.is_some() {
                 visibility.is_visible = true;
                 if let Some(slot) = &slot.element {
-                    text.sections.first_mut().unwrap().value = slot.name.to_string();
+                    text.sections.first_mut().unwrap().value = localization.element_name(slot).to_string();
                 }
             }
         }
@@ -808,13 +989,18 @@ fn hide_name(
 
 fn refresh_slots(
     mut slot_query: Query<&mut Slot, Without<ToolSlot>>,
-    ui_manager: Res<UiData>,
+    mut ui_manager: ResMut<UiData>,
+    registry: Res<Registry>,
+    localization: Res<Localization>,
     mut refresh_event: EventReader<RefreshSlotsEvent>,
 ) {
     if !refresh_event.is_empty() {
+        let visible = ui_manager.visible_elements(&registry, &localization);
+        ui_manager.set_visible_count(visible.len());
+
         for mut slot in slot_query.iter_mut() {
             let index = slot.index + ui_manager.current_page * 12;
-            let element = ui_manager.known_elements.get(index as usize);
+            let element = visible.get(index as usize);
             if let Some(element) = element {
                 slot.element = Some(element.to_owned());
             } else {
@@ -905,12 +1091,12 @@ pub fn setup_ui(mut commands: Commands, asset_server: Res<AssetServer>, mut ui_i
     add_scaled_pixel_asset(&mut commands, &asset_server, "sprites/page_down.png", 9, 9, SpriteBundle {
         transform: Transform::from_xyz(-180.0, -276.0, TOP_LEVEL),
         ..default()
-    }).insert(Name::new("Page Down")).insert(PageDown);
+    }).insert(Name::new("Page Down")).insert(PageDown).insert(Focusable::default());
 
     add_scaled_pixel_asset(&mut commands, &asset_server, "sprites/page_up.png", 9, 9, SpriteBundle {
         transform: Transform::from_xyz(-180.0, 292.0, TOP_LEVEL),
         ..default()
-    }).insert(Name::new("Page Up")).insert(PageUp);
+    }).insert(Name::new("Page Up")).insert(PageUp).insert(Focusable::default());
 
     //BG Image
     add_scaled_pixel_asset(&mut commands, &asset_server, "sprites/tavern_bg.png", 160, 90, SpriteBundle {
@@ -923,6 +1109,8 @@ pub fn setup_ui(mut commands: Commands, asset_server: Res<AssetServer>, mut ui_i
         transform: Transform::from_xyz(-400.0, 8.0, UI_LEVEL),
         ..default()
     }).insert(Name::new("Element Book"));
+
+    spawn_view_labels(&mut commands, &asset_server);
 }
 
 fn add_slot_array(commands: &mut Commands, x: f32, y: f32, width: u32, height: u32, slot_size: f32) -> u32 {
@@ -937,7 +1125,8 @@ fn add_slot_array(commands: &mut Commands, x: f32, y: f32, width: u32, height: u
                 },
                 ..default()
             })
-                .insert(Slot::with_index(wx + hy * width));
+                .insert(Slot::with_index(wx + hy * width))
+                .insert(Focusable::default());
         }
     }
     width * height
@@ -960,7 +1149,9 @@ fn setup_mixer_slots(commands: &mut Commands, mut slots_taken: &mut u32, asset_s
     })
         .insert(Slot { element: None, can_change: true, index: slots_taken.clone() })
         .insert(MixerSlot1)
-        .insert(ToolSlot);
+        .insert(ToolInput(CraftType::MIXER))
+        .insert(ToolSlot)
+        .insert(Focusable::default());
 
     commands.spawn_bundle(SpriteBundle {
         transform: Transform::from_translation(slot_pos_2),
@@ -972,7 +1163,12 @@ fn setup_mixer_slots(commands: &mut Commands, mut slots_taken: &mut u32, asset_s
     })
         .insert(Slot { element: None, can_change: true, index: slots_taken.clone() + 1 })
         .insert(MixerSlot2)
-        .insert(ToolSlot);
+        .insert(ToolInput(CraftType::MIXER))
+        .insert(ToolSlot)
+        .insert(Focusable::default());
+
+    *slots_taken += 2;
+    spawn_output_slot(commands, slots_taken, CraftType::MIXER, Vec2::new(0., pos_y));
 
     // Spawn Mixer Sprite
     commands.spawn_bundle(SpriteBundle {
@@ -997,8 +1193,6 @@ fn setup_mixer_slots(commands: &mut Commands, mut slots_taken: &mut u32, asset_s
             })
                 .insert(Name::new("Mixer Front"));
         });
-
-    *slots_taken += 2;
 }
 
 fn setup_furnace_slots(commands: &mut Commands, mut slots_taken: &mut u32, asset_server: Res<AssetServer>) {
@@ -1018,7 +1212,9 @@ fn setup_furnace_slots(commands: &mut Commands, mut slots_taken: &mut u32, asset
     })
         .insert(Slot { element: None, can_change: true, index: slots_taken.clone() })
         .insert(FurnaceSlot2)
+        .insert(ToolInput(CraftType::FURNACE))
         .insert(ToolSlot)
+        .insert(Focusable::default())
         .insert(Name::new("Furnace Slot"));
 
     commands.spawn_bundle(SpriteBundle {
@@ -1031,7 +1227,12 @@ fn setup_furnace_slots(commands: &mut Commands, mut slots_taken: &mut u32, asset
     })
         .insert(Slot { element: None, can_change: true, index: slots_taken.clone() + 1 })
         .insert(FurnaceSlot1)
-        .insert(ToolSlot);
+        .insert(ToolInput(CraftType::FURNACE))
+        .insert(ToolSlot)
+        .insert(Focusable::default());
+
+    *slots_taken += 2;
+    spawn_output_slot(commands, slots_taken, CraftType::FURNACE, Vec2::new(pos_x, pos_y - 64.));
 
     // Spawn Furnace Sprite
     commands.spawn_bundle(SpriteBundle {
@@ -1066,8 +1267,6 @@ fn setup_furnace_slots(commands: &mut Commands, mut slots_taken: &mut u32, asset
             })
                 .insert(Name::new("furnace bottom over"));
         });
-
-    *slots_taken += 2;
 }
 
 fn setup_slicer_slot(commands: &mut Commands, mut slots_taken: &mut u32, asset_server: Res<AssetServer>) {
@@ -1082,8 +1281,12 @@ fn setup_slicer_slot(commands: &mut Commands, mut slots_taken: &mut u32, asset_s
         .insert(Slot { element: None, can_change: true, index: slots_taken.clone() })
         .insert(SlicerSlot)
         .insert(ToolSlot)
+        .insert(Focusable::default())
         .insert(Name::new("Slicer Slot"));
 
+    *slots_taken += 1;
+    spawn_output_slot(commands, slots_taken, CraftType::SLICER, Vec2::new(0., 264.));
+
     // Spawn Slicer Sprite
     commands.spawn_bundle(SpriteBundle {
         sprite: Sprite {
@@ -1095,6 +1298,4 @@ fn setup_slicer_slot(commands: &mut Commands, mut slots_taken: &mut u32, asset_s
         ..default()
     })
         .insert(Name::new("Slicer Sprite"));
-
-    *slots_taken += 1;
 }
\ No newline at end of file
@@ -0,0 +1,189 @@
+use bevy::prelude::*;
+use crate::element::Element;
+use crate::game::GameManager;
+use crate::GameHelper;
+use crate::npc::{npc_rect, NpcSprite, SpotIndex};
+use crate::ui::{CraftType, DropElementEvent, Rect, Slot, ToolSlot, UiData, UpdateSlotEvent};
+use crate::ui::output::{OutputSlot, spawn_returning_element};
+
+pub const DOUBLE_CLICK_SECONDS: f64 = 0.3;
+
+const HIGHLIGHT_PADDING: f32 = 12.;
+const HIGHLIGHT_COLOR: Color = Color::rgba(1.0, 0.9, 0.5, 0.3);
+const HIGHLIGHT_HOVER_ALPHA: f32 = 0.65;
+
+/// Which tool an input slot belongs to, so slots of the same tool can trade places.
+#[derive(Component)]
+pub struct ToolInput(pub CraftType);
+
+/// Where the element being dragged was picked up, and what happened on the last few clicks.
+#[derive(Default)]
+pub struct DragState {
+    pub origin: Option<DragOrigin>,
+    pub last_click: Option<(u32, f64)>,
+    pub right_drag: Option<(u32, Element)>,
+}
+
+#[derive(Clone, Copy)]
+pub struct DragOrigin {
+    pub pos: Vec3,
+    // tool slots get their element back, the book doesn't need to be told
+    pub slot: Option<u32>,
+}
+
+impl DragState {
+    pub fn is_double_click(&mut self, slot: u32, now: f64) -> bool {
+        let double = self.last_click.is_some_and(|(last, time)| last == slot && now - time < DOUBLE_CLICK_SECONDS);
+        self.last_click = if double { None } else { Some((slot, now)) };
+        double
+    }
+}
+
+/// Sends an element to the first empty tool slot.
+#[derive(Debug)]
+pub struct QuickSendEvent(pub Element);
+
+/// Swaps what's in a tool slot with the other slot of the same tool.
+#[derive(Debug)]
+pub struct SwapToolSlotEvent(pub u32);
+
+fn accepts_drops(slot: &Slot, output: Option<&OutputSlot>) -> bool {
+    slot.can_change && output.is_none()
+}
+
+//==================================================================================================
+//                          Drop Targets
+//==================================================================================================
+
+#[derive(Component)]
+pub struct DropHighlight(Entity);
+
+pub fn highlight_drop_targets(
+    mut commands: Commands,
+    time: Res<Time>,
+    ui_data: Res<UiData>,
+    game: Res<GameManager>,
+    game_helper: Res<GameHelper>,
+    slots: Query<(Entity, &GlobalTransform, &Sprite, &Slot, Option<&OutputSlot>), (With<ToolSlot>, Without<DropHighlight>)>,
+    npcs: Query<(Entity, &GlobalTransform, &TextureAtlasSprite, &SpotIndex), With<NpcSprite>>,
+    mut highlights: Query<(Entity, &DropHighlight, &mut Transform, &mut Sprite, &mut Visibility)>,
+) {
+    if ui_data.currently_dragging.is_none() {
+        for (entity, _, _, _, _) in highlights.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+
+    // spawn the highlights on the first frame of a drag, they follow their targets after that
+    if highlights.is_empty() {
+        let targets = slots.iter()
+            .filter(|(_, _, _, slot, output)| accepts_drops(slot, *output))
+            .map(|(entity, _, _, _, _)| entity)
+            .chain(npcs.iter().map(|(entity, _, _, _)| entity));
+
+        for target in targets {
+            commands.spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: HIGHLIGHT_COLOR,
+                    ..default()
+                },
+                ..default()
+            })
+                .insert(DropHighlight(target))
+                .insert(Name::new("Drop Highlight"));
+        }
+        return;
+    }
+
+    let pulse = (time.seconds_since_startup() as f32 * 6.).sin() * 0.1;
+    let mouse = game_helper.mouse_world_pos();
+    for (entity, highlight, mut transform, mut sprite, mut visibility) in highlights.iter_mut() {
+        let target = if let Ok((_, target_transform, target_sprite, _, _)) = slots.get(highlight.0) {
+            target_sprite.custom_size.map(|size| (target_transform.translation(), size, true))
+        } else if let Ok((_, target_transform, target_sprite, spot)) = npcs.get(highlight.0) {
+            let rect = npc_rect(target_transform, target_sprite);
+            let size = Vec2::new(rect.x2 - rect.x1, rect.y1 - rect.y2);
+            Some((target_transform.translation(), size, game.npc_data.npc_at(spot.0).is_some()))
+        } else {
+            None
+        };
+
+        match target {
+            Some((pos, size, active)) => {
+                let hovered = Rect::from_center(pos.truncate(), size).is_within(mouse);
+                visibility.is_visible = active;
+                transform.translation = pos - Vec3::Z * 0.5;
+                sprite.custom_size = Some(size + Vec2::splat(HIGHLIGHT_PADDING));
+                sprite.color.set_a(if hovered { HIGHLIGHT_HOVER_ALPHA } else { HIGHLIGHT_COLOR.a() + pulse });
+            }
+            None => commands.entity(entity).despawn_recursive(),
+        }
+    }
+}
+
+pub fn snap_back_drops(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game: Res<GameManager>,
+    mut drag_state: ResMut<DragState>,
+    slots: Query<(&GlobalTransform, &Sprite, &Slot, Option<&OutputSlot>)>,
+    npcs: Query<(&GlobalTransform, &TextureAtlasSprite, &SpotIndex), With<NpcSprite>>,
+    mut element_drop_event: EventReader<DropElementEvent>,
+) {
+    for event in element_drop_event.iter() {
+        let origin = match drag_state.origin.take() {
+            Some(origin) => origin,
+            None => continue,
+        };
+
+        let on_slot = slots.iter().any(|(transform, sprite, slot, output)| {
+            accepts_drops(slot, output) && Slot::generate_rect(transform, sprite).is_within(event.0)
+        });
+        let on_npc = npcs.iter().any(|(transform, sprite, spot)| {
+            game.npc_data.npc_at(spot.0).is_some() && npc_rect(transform, sprite).is_within(event.0)
+        });
+
+        if !on_slot && !on_npc {
+            spawn_returning_element(&mut commands, &asset_server, event.1.clone(), event.0.extend(0.), origin.pos, origin.slot);
+        }
+    }
+}
+
+//==================================================================================================
+//                          Quick Send and Swap
+//==================================================================================================
+
+pub fn on_quick_send(
+    mut slots: Query<(&mut Slot, Option<&OutputSlot>), With<ToolSlot>>,
+    mut quick_send_event: EventReader<QuickSendEvent>,
+) {
+    for event in quick_send_event.iter() {
+        let free = slots.iter_mut()
+            .filter(|(slot, output)| accepts_drops(slot, *output) && slot.element.is_none())
+            .min_by_key(|(slot, _)| slot.index);
+
+        if let Some((mut slot, _)) = free {
+            slot.element = Some(event.0.clone());
+        }
+    }
+}
+
+pub fn on_swap_tool_slot(
+    slots: Query<(&Slot, &ToolInput)>,
+    mut swap_event: EventReader<SwapToolSlotEvent>,
+    mut update_slot_event: EventWriter<UpdateSlotEvent>,
+) {
+    for event in swap_event.iter() {
+        let (slot, tool) = match slots.iter().find(|(slot, _)| slot.index == event.0) {
+            Some(found) => found,
+            None => continue,
+        };
+
+        let other = slots.iter().find(|(other, other_tool)| other_tool.0 == tool.0 && other.index != slot.index);
+        if let Some((other, _)) = other {
+            update_slot_event.send(UpdateSlotEvent(slot.index, other.element.clone()));
+            update_slot_event.send(UpdateSlotEvent(other.index, slot.element.clone()));
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use bevy::prelude::*;
+use bevy::text::Text2dBounds;
+use crate::boss_fight::BossTimer;
+use crate::difficulty::Difficulty;
+use crate::element::Element;
+use crate::locale::Localization;
+use crate::registry::Registry;
+use crate::ui::{CraftFailedEvent, CraftType, FailBlinker, TEXT_LEVEL, ToolBlinker, UiData};
+
+const HINT_SECONDS: f32 = 3.5;
+// the hint fades out over the last part of its life
+const HINT_FADE_SECONDS: f32 = 1.;
+const HINT_WIDTH: f32 = 360.;
+
+const TOOLS: [CraftType; 3] = [CraftType::MIXER, CraftType::FURNACE, CraftType::SLICER];
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum CraftHint {
+    /// The same ingredients make something in a different tool.
+    OtherTool(CraftType),
+    /// Fuel and object are the wrong way around.
+    SwapSlots,
+    /// One of the ingredients has a recipe in this tool, just not with the other one.
+    WrongPartner { usable: Element, partner: Element },
+    /// Nothing here takes the ingredient, but another tool does.
+    UsableElsewhere { element: Element, tool: CraftType },
+}
+
+impl CraftHint {
+    pub fn text(&self, localization: &Localization) -> String {
+        match self {
+            CraftHint::OtherTool(tool) => localization.format("That might work in the {} instead.", &[localization.text(tool.name())]),
+            CraftHint::SwapSlots => localization.text("So close! Try swapping the top and bottom.").to_string(),
+            CraftHint::WrongPartner { usable, partner } => localization.format("{} can be used here, but not with {}.", &[localization.element_name(usable), localization.element_name(partner)]),
+            CraftHint::UsableElsewhere { element, tool } => localization.format("{} is no good here, try the {}.", &[localization.element_name(element), localization.text(tool.name())]),
+        }
+    }
+
+    /// Whether the player was close enough for the cookbook to remember it.
+    pub fn is_near_miss(&self) -> bool {
+        !matches!(self, CraftHint::UsableElsewhere { .. })
+    }
+}
+
+/// A failed craft that was almost right.
+#[derive(Clone, PartialEq, Debug)]
+pub struct NearMiss {
+    pub tool: CraftType,
+    pub elements: Vec<Element>,
+    pub hint: CraftHint,
+}
+
+impl NearMiss {
+    pub fn involves(&self, element: &Element) -> bool {
+        self.elements.contains(element)
+    }
+}
+
+/// Works out the most useful thing to tell the player after `elements` failed in `tool`.
+pub fn hint_for(registry: &Registry, tool: CraftType, elements: &[Element]) -> Option<CraftHint> {
+    if let Some(other) = TOOLS.iter().find(|other| **other != tool && registry.craft(**other, elements).is_some()) {
+        return Some(CraftHint::OtherTool(*other));
+    }
+
+    let reversed: Vec<Element> = elements.iter().rev().cloned().collect();
+    if tool == CraftType::FURNACE && registry.craft(tool, &reversed).is_some() {
+        return Some(CraftHint::SwapSlots);
+    }
+
+    if let [first, second] = elements {
+        for (usable, partner) in [(first, second), (second, first)] {
+            if registry.can_use(tool, usable) {
+                return Some(CraftHint::WrongPartner { usable: usable.clone(), partner: partner.clone() });
+            }
+        }
+    }
+
+    elements.iter().find_map(|element| {
+        TOOLS.iter()
+            .find(|other| **other != tool && registry.can_use(**other, element))
+            .map(|other| CraftHint::UsableElsewhere { element: element.clone(), tool: *other })
+    })
+}
+
+//==================================================================================================
+//                          Hint Text
+//==================================================================================================
+
+#[derive(Component)]
+pub struct HintText {
+    tool: CraftType,
+    timer: Timer,
+}
+
+pub fn show_craft_hints(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    registry: Res<Registry>,
+    difficulty: Res<Difficulty>,
+    localization: Res<Localization>,
+    mut ui_data: ResMut<UiData>,
+    boss_timers: Query<&BossTimer>,
+    blinkers: Query<(&Transform, &ToolBlinker), With<FailBlinker>>,
+    hints: Query<(Entity, &HintText)>,
+    mut craft_failed_event: EventReader<CraftFailedEvent>,
+) {
+    let allows_hints = difficulty.allows_hints() || !boss_timers.iter().any(|timer| timer.is_ticking());
+
+    for event in craft_failed_event.iter() {
+        let hint = if allows_hints { hint_for(&registry, event.0, &event.1) } else { None };
+
+        if let Some(hint) = hint.as_ref().filter(|hint| hint.is_near_miss()) {
+            ui_data.record_near_miss(NearMiss {
+                tool: event.0,
+                elements: event.1.clone(),
+                hint: hint.clone(),
+            });
+        }
+
+        // a tool only ever shows its latest hint
+        for (entity, text) in hints.iter() {
+            if text.tool == event.0 {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+
+        let pos = match blinkers.iter().find(|(_, blinker)| blinker.0 == event.0) {
+            Some((transform, _)) => transform.translation,
+            None => continue,
+        };
+
+        // the red cross alone is easy to miss, so a failed craft always says so in words
+        let message = hint.map_or_else(|| localization.text("That didn't work.").to_string(), |hint| hint.text(&localization));
+        commands.spawn_bundle(Text2dBundle {
+            text: Text::from_section(message, TextStyle {
+                font: asset_server.load("fonts/pixel_font.ttf"),
+                font_size: 20.,
+                color: Color::WHITE,
+            }).with_alignment(TextAlignment::CENTER),
+            text_2d_bounds: Text2dBounds {
+                size: Vec2::new(HINT_WIDTH, 80.),
+            },
+            transform: Transform::from_xyz(pos.x, pos.y - 64., TEXT_LEVEL),
+            ..default()
+        })
+            .insert(HintText {
+                tool: event.0,
+                timer: Timer::from_seconds(HINT_SECONDS, false),
+            })
+            .insert(Name::new("Craft Hint"));
+    }
+}
+
+pub fn fade_craft_hints(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut hints: Query<(Entity, &mut HintText, &mut Text)>,
+) {
+    for (entity, mut hint, mut text) in hints.iter_mut() {
+        hint.timer.tick(time.delta());
+        if hint.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let remaining = HINT_SECONDS - hint.timer.elapsed_secs();
+        text.sections[0].style.color.set_a((remaining / HINT_FADE_SECONDS).min(1.));
+    }
+}
@@ -0,0 +1,295 @@
+use bevy::prelude::*;
+use bevy::text::Text2dSize;
+use crate::element::{Category, Element, Tag};
+use crate::focus::Focus;
+use crate::GameHelper;
+use crate::locale::Localization;
+use crate::registry::Registry;
+use crate::ui::{CraftType, Rect, RefreshSlotsEvent, TEXT_LEVEL, UiData};
+
+// the element book on the left side of the screen, typing while hovering it searches
+pub const BOOK_CENTER: Vec2 = Vec2::new(-400., 8.);
+const BOOK_SIZE: Vec2 = Vec2::new(60. * 8., 82. * 8.);
+const MAX_SEARCH_LENGTH: usize = 16;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SortMode {
+    Discovery,
+    Alphabetical,
+    Tool,
+    RawFirst,
+}
+
+impl SortMode {
+    fn next(self) -> Self {
+        match self {
+            SortMode::Discovery => SortMode::Alphabetical,
+            SortMode::Alphabetical => SortMode::Tool,
+            SortMode::Tool => SortMode::RawFirst,
+            SortMode::RawFirst => SortMode::Discovery,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            SortMode::Discovery => "Oldest first",
+            SortMode::Alphabetical => "A-Z",
+            SortMode::Tool => "Tool",
+            SortMode::RawFirst => "Raw first",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ElementFilter {
+    All,
+    Raw,
+    Crafted,
+    MadeWith(CraftType),
+    Category(Category),
+}
+
+impl ElementFilter {
+    fn next(self) -> Self {
+        match self {
+            ElementFilter::All => ElementFilter::Raw,
+            ElementFilter::Raw => ElementFilter::Crafted,
+            ElementFilter::Crafted => ElementFilter::MadeWith(CraftType::FURNACE),
+            ElementFilter::MadeWith(CraftType::FURNACE) => ElementFilter::MadeWith(CraftType::SLICER),
+            ElementFilter::MadeWith(CraftType::SLICER) => ElementFilter::MadeWith(CraftType::MIXER),
+            ElementFilter::MadeWith(CraftType::MIXER) => ElementFilter::Category(Category::Dish),
+            ElementFilter::Category(Category::Dish) => ElementFilter::Category(Category::Utensil),
+            ElementFilter::Category(_) => ElementFilter::All,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            ElementFilter::All => "All",
+            ElementFilter::Raw => "Raw",
+            ElementFilter::Crafted => "Crafted",
+            ElementFilter::MadeWith(tool) => tool.name(),
+            ElementFilter::Category(Category::Dish) => "Dishes",
+            ElementFilter::Category(Category::Utensil) => "Utensils",
+            ElementFilter::Category(Category::Ingredient) => "Ingredients",
+            ElementFilter::Category(Category::Component) => "Components",
+        }
+    }
+
+    fn matches(&self, element: &Element, made_with: Option<CraftType>) -> bool {
+        match self {
+            ElementFilter::All => true,
+            ElementFilter::Raw => made_with.is_none(),
+            ElementFilter::Crafted => made_with.is_some(),
+            ElementFilter::MadeWith(tool) => made_with == Some(*tool),
+            ElementFilter::Category(category) => element.category == *category,
+        }
+    }
+}
+
+/// The tool that makes `element`, or `None` for raw ingredients.
+fn made_with(element: &Element, registry: &Registry) -> Option<CraftType> {
+    registry.recipes_for(element).first().map(|recipe| recipe.tool)
+}
+
+fn tool_order(tool: Option<CraftType>) -> u32 {
+    match tool {
+        None => 0,
+        Some(CraftType::FURNACE) => 1,
+        Some(CraftType::SLICER) => 2,
+        Some(CraftType::MIXER) => 3,
+    }
+}
+
+/// Cycles through every tag, `None` being any tag.
+fn next_tag(tag: Option<Tag>) -> Option<Tag> {
+    match tag {
+        None => Tag::TAGS.first().copied(),
+        Some(tag) => Tag::TAGS.iter().skip_while(|other| **other != tag).nth(1).copied(),
+    }
+}
+
+/// How the element book is searched, sorted and filtered.
+pub struct InventoryView {
+    pub search: String,
+    pub sort: SortMode,
+    pub filter: ElementFilter,
+    pub tag: Option<Tag>,
+    pub search_focused: bool,
+}
+
+impl Default for InventoryView {
+    fn default() -> Self {
+        InventoryView {
+            search: String::new(),
+            sort: SortMode::Discovery,
+            filter: ElementFilter::All,
+            tag: None,
+            search_focused: false,
+        }
+    }
+}
+
+impl InventoryView {
+    // `is_none_or` is too new for the toolchains the game still builds on
+    #[allow(clippy::unnecessary_map_or)]
+    pub fn apply(&self, elements: &[Element], registry: &Registry, localization: &Localization) -> Vec<Element> {
+        let search = self.search.to_lowercase();
+        // looking up the recipes is the slow part, so it is done once per element
+        let mut view: Vec<(usize, Option<CraftType>, Element)> = elements.iter().cloned().enumerate()
+            .filter(|(_, element)| search.is_empty() || localization.element_name(element).to_lowercase().contains(&search))
+            .filter(|(_, element)| self.tag.map_or(true, |tag| element.has_tag(tag)))
+            .map(|(index, element)| (index, made_with(&element, registry), element))
+            .filter(|(_, made_with, element)| self.filter.matches(element, *made_with))
+            .collect();
+
+        // ties always fall back to discovery order
+        match self.sort {
+            SortMode::Discovery => {}
+            SortMode::Alphabetical => view.sort_by(|(_, _, a), (_, _, b)| localization.element_name(a).cmp(localization.element_name(b))),
+            SortMode::Tool => view.sort_by_key(|(index, made_with, _)| (tool_order(*made_with), *index)),
+            SortMode::RawFirst => view.sort_by_key(|(index, made_with, _)| (made_with.is_some(), *index)),
+        }
+
+        view.into_iter().map(|(_, _, element)| element).collect()
+    }
+}
+
+//==================================================================================================
+//                          Labels
+//==================================================================================================
+
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum ViewLabel {
+    Search,
+    Sort,
+    Filter,
+    Tag,
+    Output,
+}
+
+pub fn spawn_view_labels(commands: &mut Commands, asset_server: &Res<AssetServer>) {
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/pixel_font.ttf"),
+        font_size: 22.0,
+        color: Color::rgb(0.54, 0.23, 0.12),
+    };
+    let text_alignment = TextAlignment {
+        vertical: VerticalAlign::Top,
+        horizontal: HorizontalAlign::Left,
+    };
+
+    let labels = [
+        (ViewLabel::Search, Vec2::new(-610., 334.), "Search Label"),
+        (ViewLabel::Sort, Vec2::new(-610., 306.), "Sort Label"),
+        (ViewLabel::Filter, Vec2::new(-440., 306.), "Filter Label"),
+        (ViewLabel::Tag, Vec2::new(-360., 334.), "Tag Label"),
+        (ViewLabel::Output, Vec2::new(-280., 306.), "Output Label"),
+    ];
+
+    for (label, pos, name) in labels {
+        commands.spawn_bundle(Text2dBundle {
+            text: Text::from_section("", text_style.clone()).with_alignment(text_alignment),
+            transform: Transform::from_xyz(pos.x, pos.y, TEXT_LEVEL),
+            ..default()
+        })
+            .insert(label)
+            .insert(Name::new(name));
+    }
+}
+
+//==================================================================================================
+//                          Systems
+//==================================================================================================
+
+pub fn type_search(
+    game_helper: Res<GameHelper>,
+    focus: Res<Focus>,
+    keys: Res<Input<KeyCode>>,
+    mut ui_data: ResMut<UiData>,
+    mut received_characters: EventReader<ReceivedCharacter>,
+    mut refresh_slots: EventWriter<RefreshSlotsEvent>,
+) {
+    // the keyboard cursor and open menus need the keys for themselves
+    let focused = !focus.is_active() && !game_helper.is_menu_open() && Rect::from_center(BOOK_CENTER, BOOK_SIZE).is_within(game_helper.mouse_world_pos());
+    if ui_data.view.search_focused != focused {
+        ui_data.view.search_focused = focused;
+    }
+
+    if !focused {
+        received_characters.clear();
+        return;
+    }
+
+    let mut search = ui_data.view.search.clone();
+    for event in received_characters.iter() {
+        if (event.char.is_alphanumeric() || event.char == ' ') && search.len() < MAX_SEARCH_LENGTH {
+            search.push(event.char);
+        }
+    }
+
+    if keys.just_pressed(KeyCode::Back) {
+        search.pop();
+    }
+
+    if search != ui_data.view.search {
+        ui_data.view.search = search;
+        ui_data.current_page = 0;
+        refresh_slots.send(RefreshSlotsEvent);
+    }
+}
+
+pub fn click_view_labels(
+    labels: Query<(&GlobalTransform, &Text2dSize, &ViewLabel)>,
+    game_helper: Res<GameHelper>,
+    mouse: Res<Input<MouseButton>>,
+    mut ui_data: ResMut<UiData>,
+    mut refresh_slots: EventWriter<RefreshSlotsEvent>,
+) {
+    if !mouse.just_pressed(MouseButton::Left) || game_helper.is_menu_open() {
+        return;
+    }
+
+    for (transform, size, label) in labels.iter() {
+        let pos = transform.translation();
+        let rect = Rect::new(pos.x, pos.y, pos.x + size.size.x, pos.y - size.size.y);
+        if !rect.is_within(game_helper.mouse_world_pos()) {
+            continue;
+        }
+
+        match label {
+            ViewLabel::Search => ui_data.view.search.clear(),
+            ViewLabel::Sort => ui_data.view.sort = ui_data.view.sort.next(),
+            ViewLabel::Filter => ui_data.view.filter = ui_data.view.filter.next(),
+            ViewLabel::Tag => ui_data.view.tag = next_tag(ui_data.view.tag),
+            ViewLabel::Output => ui_data.output_to_slot = !ui_data.output_to_slot,
+        }
+        ui_data.current_page = 0;
+        refresh_slots.send(RefreshSlotsEvent);
+    }
+}
+
+pub fn render_view_labels(
+    ui_data: Res<UiData>,
+    localization: Res<Localization>,
+    mut labels: Query<(&mut Text, &ViewLabel)>,
+) {
+    if !ui_data.is_changed() && !localization.is_changed() {
+        return;
+    }
+
+    let view = &ui_data.view;
+    let label = |name: &str, value: &str| format!("{}: {}", localization.text(name), localization.text(value));
+    for (mut text, view_label) in labels.iter_mut() {
+        text.sections[0].value = match view_label {
+            ViewLabel::Search => {
+                let cursor = if view.search_focused { "_" } else { "" };
+                format!("{}: {}{}", localization.text("Search"), view.search, cursor)
+            }
+            ViewLabel::Sort => label("Sort", view.sort.label()),
+            ViewLabel::Filter => label("Show", view.filter.label()),
+            ViewLabel::Tag => label("Tag", view.tag.map_or("Any", |tag| tag.name())),
+            ViewLabel::Output => label("Results", if ui_data.output_to_slot { "Tool" } else { "Book" }),
+        };
+    }
+}
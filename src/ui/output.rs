@@ -0,0 +1,131 @@
+use bevy::prelude::*;
+use crate::element::Element;
+use crate::focus::Focusable;
+use crate::ui::{CraftType, DRAG_LEVEL, InsertElementEvent, SLOT_LEVEL, Slot, ToolSlot, UpdateSlotEvent};
+use crate::ui::inventory::BOOK_CENTER;
+
+const OUTPUT_SLOT_SIZE: f32 = 96.;
+// how far right of the tool's center the output slot sits
+const OUTPUT_SLOT_OFFSET: f32 = 224.;
+
+const RETURN_SECONDS: f32 = 0.4;
+const RETURN_END_SIZE: f32 = 48.;
+
+/// Where a tool puts what it made when results go to the tool instead of the book.
+#[derive(Component)]
+pub struct OutputSlot(pub CraftType);
+
+/// A crafted element waiting in the tool's output slot.
+#[derive(Debug)]
+pub struct OutputElementEvent(pub CraftType, pub Element);
+
+/// An ingredient from a failed craft, flying back to the book from where it was dropped.
+#[derive(Debug)]
+pub struct ReturnElementEvent(pub Element, pub Vec3);
+
+pub fn spawn_output_slot(commands: &mut Commands, slots_taken: &mut u32, tool: CraftType, tool_pos: Vec2) {
+    commands.spawn_bundle(SpriteBundle {
+        transform: Transform::from_xyz(tool_pos.x + OUTPUT_SLOT_OFFSET, tool_pos.y, SLOT_LEVEL),
+        sprite: Sprite {
+            custom_size: Some(Vec2::splat(OUTPUT_SLOT_SIZE)),
+            ..default()
+        },
+        ..default()
+    })
+        .insert(Slot { element: None, can_change: true, index: *slots_taken })
+        .insert(OutputSlot(tool))
+        .insert(ToolSlot)
+        .insert(Focusable::default())
+        .insert(Name::new(format!("{} Output Slot", tool.name())));
+
+    *slots_taken += 1;
+}
+
+pub fn on_output_element(
+    mut slots: Query<(&mut Slot, &OutputSlot)>,
+    mut output_element_event: EventReader<OutputElementEvent>,
+    mut insert_element_event: EventWriter<InsertElementEvent>,
+) {
+    for event in output_element_event.iter() {
+        for (mut slot, output) in slots.iter_mut() {
+            if output.0 != event.0 {
+                continue;
+            }
+
+            // whatever was left sitting in the slot goes into the book instead of being lost
+            if let Some(previous) = slot.element.replace(event.1.clone()) {
+                insert_element_event.send(InsertElementEvent(previous));
+            }
+        }
+    }
+}
+
+//==================================================================================================
+//                          Returning Ingredients
+//==================================================================================================
+
+#[derive(Component)]
+pub struct ReturningElement {
+    element: Element,
+    from: Vec3,
+    to: Vec3,
+    slot: Option<u32>,
+    timer: Timer,
+}
+
+/// Flies `element` from `from` to `to`, then puts it in the tool slot it came from, or the book.
+pub fn spawn_returning_element(commands: &mut Commands, asset_server: &Res<AssetServer>, element: Element, from: Vec3, to: Vec3, slot: Option<u32>) {
+    commands.spawn_bundle(SpriteBundle {
+        sprite: Sprite {
+            custom_size: Some(Vec2::splat(128.)),
+            ..default()
+        },
+        transform: Transform::from_xyz(from.x, from.y, DRAG_LEVEL - 1.),
+        texture: asset_server.load(element.sprite_file_path().as_str()),
+        ..default()
+    })
+        .insert(ReturningElement {
+            element,
+            from: from.truncate().extend(DRAG_LEVEL - 1.),
+            to: to.truncate().extend(DRAG_LEVEL - 1.),
+            slot,
+            timer: Timer::from_seconds(RETURN_SECONDS, false),
+        })
+        .insert(Name::new("Returning Element"));
+}
+
+pub fn on_return_element(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut return_element_event: EventReader<ReturnElementEvent>,
+) {
+    for event in return_element_event.iter() {
+        spawn_returning_element(&mut commands, &asset_server, event.0.clone(), event.1, BOOK_CENTER.extend(0.), None);
+    }
+}
+
+pub fn animate_returning_elements(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut ReturningElement, &mut Transform, &mut Sprite)>,
+    mut insert_element_event: EventWriter<InsertElementEvent>,
+    mut update_slot_event: EventWriter<UpdateSlotEvent>,
+) {
+    for (entity, mut returning, mut transform, mut sprite) in query.iter_mut() {
+        returning.timer.tick(time.delta());
+        // ease out, so it leaves quickly and settles into place
+        let progress = 1. - (1. - returning.timer.percent()).powi(2);
+
+        transform.translation = returning.from.lerp(returning.to, progress);
+        let end_size = if returning.slot.is_some() { 128. } else { RETURN_END_SIZE };
+        sprite.custom_size = Some(Vec2::splat(128. + (end_size - 128.) * progress));
+
+        if returning.timer.finished() {
+            match returning.slot {
+                Some(slot) => update_slot_event.send(UpdateSlotEvent(slot, Some(returning.element.clone()))),
+                None => insert_element_event.send(InsertElementEvent(returning.element.clone())),
+            }
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}